@@ -21,9 +21,12 @@ impl Default for Location {
         Location::new(0, 0)
     }
 }
+/// `m.a[0] += x` has the keys `"a"` and `0` as its path and `+` as its operator
 #[derive(Debug, PartialEq, Clone)]
 pub struct AssignmentExpr {
     pub symbol: String,
+    pub path: Vec<Expr>,
+    pub operator: Option<BinaryOperator>,
     pub value: Box<Expr>,
     pub location: Location,
 }
@@ -33,8 +36,12 @@ pub enum BinaryOperator {
     Sub,
     Mul,
     Div,
+    IntDiv, // Division truncated to an integer
+    Mod,    // Remainder
+    Pow,
     Gt, // Greater than
     Eq, // Equal
+    Ne, // Not equal
     Ge, // Greater or equal than
     Lt, // Less than
     Le, // Less of equal than
@@ -67,9 +74,18 @@ impl BinaryExpr {
     }
 }
 #[derive(Debug, PartialEq, Clone)]
+pub struct Range {
+    pub start: Expr,
+    pub end: Expr,
+    pub step: Option<Expr>,
+    pub inclusive: bool,
+    pub location: Location,
+}
+#[derive(Debug, PartialEq, Clone)]
 pub enum UnaryOperator {
     Not,
     BitwiseNot,
+    Neg,
 }
 #[derive(Debug, PartialEq, Clone)]
 pub struct UnaryExpr {
@@ -79,13 +95,13 @@ pub struct UnaryExpr {
 }
 #[derive(Debug, PartialEq, Clone)]
 pub struct Call {
-    pub symbol: String,
+    pub callee: Box<Expr>,
     pub args: Vec<Expr>,
     pub location: Location,
 }
 #[derive(Debug, PartialEq, Clone)]
 pub struct Closure {
-    pub params: Vec<String>,
+    pub params: Vec<Pattern>,
     pub body: Program,
     pub location: Location,
 }
@@ -102,9 +118,84 @@ pub struct SubExpr {
 #[derive(Debug, PartialEq, Clone)]
 pub struct DestructuringAsignment {
     pub location: Location,
-    pub symbols: Vec<Symbol>,
+    pub pattern: Pattern,
     pub value: Box<Expr>,
 }
+/// Member access keeps its key as a literal, so `m.key` is stored the same as `m["key"]`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Access {
+    pub target: Expr,
+    pub key: Expr,
+    pub location: Location,
+}
+#[derive(Debug, PartialEq, Clone)]
+pub struct StringInterpolation {
+    pub parts: Vec<Expr>,
+    pub location: Location,
+}
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+    Wildcard(Location),
+    Binding(Symbol),
+    Literal(Literal),
+    Tuple(TuplePattern),
+    Vector(VectorPattern),
+    Map(MapPattern),
+}
+impl Pattern {
+    pub fn get_location(&self) -> Location {
+        match self {
+            Pattern::Wildcard(location) => *location,
+            Pattern::Binding(val) => val.location,
+            Pattern::Literal(val) => val.get_location(),
+            Pattern::Tuple(val) => val.location,
+            Pattern::Vector(val) => val.location,
+            Pattern::Map(val) => val.location,
+        }
+    }
+}
+#[derive(Debug, PartialEq, Clone)]
+pub struct TuplePattern {
+    pub items: Vec<Pattern>,
+    pub location: Location,
+}
+#[derive(Debug, PartialEq, Clone)]
+pub struct VectorPattern {
+    pub items: Vec<Pattern>,
+    pub rest: Option<RestPattern>,
+    pub location: Location,
+}
+/// `...rest` binds a vector with the remaining elements, while `...` just skips them
+#[derive(Debug, PartialEq, Clone)]
+pub struct RestPattern {
+    pub symbol: Option<Symbol>,
+    pub location: Location,
+}
+/// A key without a pattern binds its value to a symbol with the same name
+#[derive(Debug, PartialEq, Clone)]
+pub struct MapPattern {
+    pub fields: Vec<(String, Pattern)>,
+    pub location: Location,
+}
+#[derive(Debug, PartialEq, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expr>,
+    pub value: Expr,
+    pub location: Location,
+}
+#[derive(Debug, PartialEq, Clone)]
+pub struct Match {
+    pub value: Expr,
+    pub arms: Vec<MatchArm>,
+    pub location: Location,
+}
+/// Evaluates to its last statement when that's an expression or an `if`, and to `Void` otherwise
+#[derive(Debug, PartialEq, Clone)]
+pub struct Block {
+    pub body: Program,
+    pub location: Location,
+}
 #[derive(Debug, PartialEq, Clone)]
 pub enum Expr {
     BinaryExpr(Box<BinaryExpr>),
@@ -115,6 +206,12 @@ pub enum Expr {
     Literal(Literal),
     SubExpr(SubExpr),
     DestructuringAsignment(DestructuringAsignment),
+    StringInterpolation(StringInterpolation),
+    Access(Box<Access>),
+    Range(Box<Range>),
+    Match(Box<Match>),
+    If(Box<If>),
+    Block(Block),
 }
 impl Expr {
     pub fn get_location(&self) -> Location {
@@ -127,6 +224,12 @@ impl Expr {
             Expr::Literal(val) => val.get_location(),
             Expr::SubExpr(val) => val.location,
             Expr::DestructuringAsignment(val) => val.location,
+            Expr::StringInterpolation(val) => val.location,
+            Expr::Access(val) => val.location,
+            Expr::Range(val) => val.location,
+            Expr::Match(val) => val.location,
+            Expr::If(val) => val.location,
+            Expr::Block(val) => val.location,
         }
     }
 }
@@ -211,6 +314,7 @@ impl Literal {
         }
     }
 }
+/// An `else if` is stored as an else block holding a single `if`
 #[derive(Debug, PartialEq, Clone)]
 pub struct If {
     pub cond: Expr,
@@ -220,12 +324,22 @@ pub struct If {
 }
 #[derive(Debug, PartialEq, Clone)]
 pub struct While {
+    pub label: Option<String>,
     pub cond: Expr,
     pub body: Program,
     pub location: Location,
 }
 #[derive(Debug, PartialEq, Clone)]
+pub struct ForIn {
+    pub label: Option<String>,
+    pub pattern: Pattern,
+    pub iterable: Expr,
+    pub body: Program,
+    pub location: Location,
+}
+#[derive(Debug, PartialEq, Clone)]
 pub struct For {
+    pub label: Option<String>,
     pub cond: Expr,
     pub body: Program,
     pub init: Stmt,
@@ -239,12 +353,38 @@ pub struct Return {
     pub location: Location,
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct Break {
+    pub label: Option<String>,
+    pub location: Location,
+}
+#[derive(Debug, PartialEq, Clone)]
+pub struct Continue {
+    pub label: Option<String>,
+    pub location: Location,
+}
+#[derive(Debug, PartialEq, Clone)]
+pub struct Nonlocal {
+    pub symbols: Vec<String>,
+    pub location: Location,
+}
+#[derive(Debug, PartialEq, Clone)]
+pub struct Global {
+    pub symbols: Vec<String>,
+    pub location: Location,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Stmt {
     Return(Return),
     If(If),
     While(While),
     For(Box<For>),
+    ForIn(Box<ForIn>),
+    Break(Break),
+    Continue(Continue),
+    Nonlocal(Nonlocal),
+    Global(Global),
     Expr(Expr),
 }
 
@@ -9,6 +9,12 @@ use run_file::{error::RunfileResult, run_file};
 fn main() -> RunfileResult {
     let cli = Command::new("cli")
         .arg(Arg::new("file_path").required(true).action(ArgAction::Set))
+        .arg(
+            Arg::new("big_ints")
+                .long("big-ints")
+                .help("Use arbitrary precision integers instead of failing on overflow")
+                .action(ArgAction::SetTrue),
+        )
         .get_matches();
     let file_path: &String = cli
         .get_one("file_path")
@@ -17,6 +23,9 @@ fn main() -> RunfileResult {
     let mut ctx = Context::new(scope);
     ctx.use_plugin(Stdlib::new());
     ctx.use_plugin(Stdio::new());
+    if cli.get_flag("big_ints") {
+        ctx.use_big_ints();
+    }
     run_file(file_path, &mut ctx)?;
     Ok(())
 }
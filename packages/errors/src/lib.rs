@@ -8,6 +8,12 @@ pub enum ErrorKind {
     NonCallable,
     InvalidOperation,
     WrongArgs,
+    OutOfBounds,
+    MissingKey,
+    Syntax,
+    Arithmetic,
+    NoMatch,
+    PatternMismatch,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -0,0 +1,253 @@
+use ast::{Access, Location};
+use errors::{DashlangError, DashlangResult, ErrorKind};
+
+use crate::{
+    ctx::Context,
+    eval,
+    scope::Scope,
+    stdlib::stdio::literal_display::stdlib_literal_display,
+    value::{Range, Value},
+};
+
+fn get_index(
+    key: &Value,
+    len: usize,
+    location: Location,
+    key_location: Location,
+) -> DashlangResult<usize> {
+    match key {
+        Value::Int(index) => match usize::try_from(*index) {
+            Ok(index) if index < len => Ok(index),
+            _ => Err(DashlangError::new(
+                &format!("Index out of bounds: the length is {len} but the index is {index}"),
+                ErrorKind::OutOfBounds,
+            )
+            .location(location)),
+        },
+        _ => Err(DashlangError::new(
+            "Expected index to be an integer",
+            ErrorKind::InvalidOperation,
+        )
+        .location(key_location)),
+    }
+}
+
+fn get_element(
+    elements: &[Value],
+    key: &Value,
+    location: Location,
+    key_location: Location,
+) -> DashlangResult<Value> {
+    let index = get_index(key, elements.len(), location, key_location)?;
+    Ok(elements[index].clone())
+}
+
+/// An empty range can start right at the end
+fn check_slice(indexes: &Range, len: usize, location: Location) -> DashlangResult<()> {
+    let in_bounds = |index: i64| usize::try_from(index).is_ok_and(|index| index < len);
+    let valid = match (indexes.get(0), indexes.last()) {
+        (Some(first), Some(last)) => in_bounds(first) && in_bounds(last),
+        _ => usize::try_from(indexes.start).is_ok_and(|start| start <= len),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(DashlangError::new(
+            &format!(
+                "Slice out of bounds: the length is {len} but the range is {}",
+                stdlib_literal_display(&Value::Range(*indexes))
+            ),
+            ErrorKind::OutOfBounds,
+        )
+        .location(location))
+    }
+}
+
+fn slice_elements(
+    elements: &[Value],
+    indexes: &Range,
+    location: Location,
+) -> DashlangResult<Vec<Value>> {
+    check_slice(indexes, elements.len(), location)?;
+    Ok(indexes
+        .iter()
+        .map(|index| elements[index as usize].clone())
+        .collect())
+}
+
+fn eval_slice(target: Value, indexes: &Range, location: Location) -> DashlangResult<Value> {
+    match target {
+        Value::Vector(vector) => Ok(Value::vector(slice_elements(
+            &vector.borrow(),
+            indexes,
+            location,
+        )?)),
+        Value::Tuple(tuple) => Ok(Value::Tuple(slice_elements(&tuple, indexes, location)?)),
+        Value::String(string) => {
+            let chars: Vec<char> = string.chars().collect();
+            check_slice(indexes, chars.len(), location)?;
+            Ok(Value::String(
+                indexes.iter().map(|index| chars[index as usize]).collect(),
+            ))
+        }
+        Value::Range(range) => {
+            check_slice(
+                indexes,
+                usize::try_from(range.len).unwrap_or(usize::MAX),
+                location,
+            )?;
+            let sliced = range.select(indexes).ok_or_else(|| {
+                DashlangError::new("Integer overflow", ErrorKind::Arithmetic).location(location)
+            })?;
+            Ok(Value::Range(sliced))
+        }
+        _ => Err(DashlangError::new(
+            "Only vectors, tuples, strings and ranges can be sliced",
+            ErrorKind::InvalidOperation,
+        )
+        .location(location)),
+    }
+}
+
+fn expect_string_key(key: &Value, key_location: Location) -> DashlangResult<&String> {
+    match key {
+        Value::String(key) => Ok(key),
+        _ => Err(DashlangError::new(
+            "Expected map key to be a string",
+            ErrorKind::InvalidOperation,
+        )
+        .location(key_location)),
+    }
+}
+
+fn not_assignable(location: Location) -> DashlangError {
+    DashlangError::new(
+        "Only vector elements and map fields can be assigned",
+        ErrorKind::InvalidOperation,
+    )
+    .location(location)
+}
+
+/// Vectors can only have existing indexes replaced, while maps also get new keys
+pub fn assign_path(
+    target: Value,
+    path: &[(Value, Location)],
+    value: Value,
+    location: Location,
+) -> DashlangResult<()> {
+    let Some(((last_key, last_key_location), parents)) = path.split_last() else {
+        return Ok(());
+    };
+    // Errors point at the part of the target that could not be assigned
+    let error_location = |key_location: Location| Location::new(location.start, key_location.end);
+    let mut target = target;
+    for (key, key_location) in parents {
+        let key_location = *key_location;
+        target = match target {
+            Value::Vector(vector) => get_element(
+                &vector.borrow(),
+                key,
+                error_location(key_location),
+                key_location,
+            )?,
+            Value::Map(map) => {
+                let key = expect_string_key(key, key_location)?;
+                let found = map.borrow().get(key).cloned();
+                found.ok_or_else(|| {
+                    DashlangError::new(
+                        &format!("Key '{key}' not found in map"),
+                        ErrorKind::MissingKey,
+                    )
+                    .location(error_location(key_location))
+                })?
+            }
+            _ => return Err(not_assignable(error_location(key_location))),
+        };
+    }
+    let key_location = *last_key_location;
+    match target {
+        Value::Vector(vector) => {
+            let mut vector = vector.borrow_mut();
+            let index = get_index(
+                last_key,
+                vector.len(),
+                error_location(key_location),
+                key_location,
+            )?;
+            vector[index] = value;
+        }
+        Value::Map(map) => {
+            let key = expect_string_key(last_key, key_location)?;
+            map.borrow_mut().insert(key.clone(), value);
+        }
+        _ => return Err(not_assignable(error_location(key_location))),
+    }
+    Ok(())
+}
+
+pub fn eval_access<T: Scope + Clone>(access: Access, ctx: &Context<T>) -> DashlangResult<Value> {
+    let key_location = access.key.get_location();
+    let target = eval(access.target, ctx)?;
+    let key = eval(access.key, ctx)?;
+    access_value(target, &key, access.location, key_location)
+}
+
+pub fn read_path(
+    target: Value,
+    path: &[(Value, Location)],
+    location: Location,
+) -> DashlangResult<Value> {
+    let mut target = target;
+    for (key, key_location) in path {
+        let access_location = Location::new(location.start, key_location.end);
+        target = access_value(target, key, access_location, *key_location)?;
+    }
+    Ok(target)
+}
+
+fn access_value(
+    target: Value,
+    key: &Value,
+    location: Location,
+    key_location: Location,
+) -> DashlangResult<Value> {
+    if let Value::Range(indexes) = key {
+        return eval_slice(target, indexes, location);
+    }
+    match target {
+        Value::Vector(vector) => get_element(&vector.borrow(), key, location, key_location),
+        Value::Tuple(tuple) => get_element(&tuple, key, location, key_location),
+        Value::String(string) => {
+            let len = string.chars().count();
+            let index = get_index(key, len, location, key_location)?;
+            let found = string
+                .chars()
+                .nth(index)
+                .expect("Index was already checked");
+            Ok(Value::String(found.to_string()))
+        }
+        Value::Range(range) => {
+            let len = usize::try_from(range.len).unwrap_or(usize::MAX);
+            let index = get_index(key, len, location, key_location)?;
+            Ok(Value::Int(
+                range.get(index as u64).expect("Index was already checked"),
+            ))
+        }
+        Value::Map(map) => {
+            let key = expect_string_key(key, key_location)?;
+            let found = map.borrow().get(key).cloned();
+            found.ok_or_else(|| {
+                DashlangError::new(
+                    &format!("Key '{key}' not found in map"),
+                    ErrorKind::MissingKey,
+                )
+                .location(location)
+            })
+        }
+        _ => Err(DashlangError::new(
+            "Only vectors, tuples, strings, ranges and maps can be accessed",
+            ErrorKind::InvalidOperation,
+        )
+        .location(location)),
+    }
+}
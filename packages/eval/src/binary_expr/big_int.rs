@@ -0,0 +1,99 @@
+use ast::BinaryOperator;
+use errors::{DashlangError, DashlangResult, ErrorKind};
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive, Zero};
+
+use crate::value::Value;
+
+pub fn as_big_int(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::Int(int) => Some(BigInt::from(*int)),
+        Value::BigInt(int) => Some(int.clone()),
+        _ => None,
+    }
+}
+
+/// Wraps an integer result, which stays a regular integer whenever it fits in one
+pub fn big_int_value(value: BigInt) -> Value {
+    match value.to_i64() {
+        Some(value) => Value::Int(value),
+        None => Value::BigInt(value),
+    }
+}
+
+pub fn big_int_to_float(value: Value) -> Value {
+    match value {
+        Value::BigInt(int) => Value::Float(int.to_f64().unwrap_or(f64::NAN)),
+        value => value,
+    }
+}
+
+/// Bit length a big integer can reach, so a huge shift or power fails instead of exhausting memory
+pub const MAX_BITS: u64 = 1 << 22;
+
+fn arithmetic_error(message: &str) -> DashlangError {
+    DashlangError::new(message, ErrorKind::Arithmetic)
+}
+
+/// Gives `None` when the operands aren't both integers, so the regular rules apply
+pub fn eval_big_int_operation(
+    operator: BinaryOperator,
+    left: &Value,
+    right: &Value,
+) -> Option<DashlangResult<Value>> {
+    let (left, right) = (as_big_int(left)?, as_big_int(right)?);
+    let shift_amount = |amount: &BigInt| {
+        amount
+            .to_usize()
+            .ok_or_else(|| arithmetic_error("Shift amount out of range"))
+    };
+    let too_large = || arithmetic_error("Integer too large");
+    let result = match operator {
+        BinaryOperator::Add => Ok(left + right),
+        BinaryOperator::Sub => Ok(left - right),
+        BinaryOperator::Mul => Ok(left * right),
+        BinaryOperator::Div | BinaryOperator::IntDiv | BinaryOperator::Mod if right.is_zero() => {
+            Err(arithmetic_error("Division by zero"))
+        }
+        BinaryOperator::Div | BinaryOperator::IntDiv => Ok(left / right),
+        BinaryOperator::Mod => Ok(left % right),
+        BinaryOperator::Pow if right.is_negative() => {
+            let base = left.to_f64().unwrap_or(f64::NAN);
+            let exponent = right.to_f64().unwrap_or(f64::NAN);
+            return Some(Ok(Value::Float(base.powf(exponent))));
+        }
+        BinaryOperator::Pow => match right.to_u32() {
+            // |left| is at least 2 ** (bits - 1), which bounds the result from below
+            Some(exponent)
+                if left
+                    .bits()
+                    .saturating_sub(1)
+                    .saturating_mul(exponent.into())
+                    > MAX_BITS =>
+            {
+                Err(too_large())
+            }
+            Some(exponent) => Ok(left.pow(exponent)),
+            None => Err(arithmetic_error("Integer overflow")),
+        },
+        BinaryOperator::BitwiseShiftLeft => shift_amount(&right).and_then(|amount| {
+            if !left.is_zero() && left.bits().saturating_add(amount as u64) > MAX_BITS {
+                Err(too_large())
+            } else {
+                Ok(left << amount)
+            }
+        }),
+        BinaryOperator::BitwiseShiftRight => shift_amount(&right).map(|amount| left >> amount),
+        BinaryOperator::BitwiseAnd => Ok(left & right),
+        BinaryOperator::BitwiseOr => Ok(left | right),
+        BinaryOperator::BitwiseXor => Ok(left ^ right),
+        _ => return None,
+    };
+    Some(result.and_then(|result| {
+        if result.bits() > MAX_BITS {
+            Err(too_large())
+        } else {
+            Ok(big_int_value(result))
+        }
+    }))
+}
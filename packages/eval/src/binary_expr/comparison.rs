@@ -0,0 +1,152 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use errors::{DashlangError, DashlangResult, ErrorKind};
+
+use super::big_int::{as_big_int, big_int_to_float};
+use crate::value::Value;
+
+/// Compares an integer with a float exactly, without rounding big integers to the nearest float
+fn cmp_int_float(int: i64, float: f64) -> Option<Ordering> {
+    const LIMIT: f64 = 9223372036854775808.0; // 2^63
+    if float.is_nan() {
+        return None;
+    }
+    if float >= LIMIT {
+        return Some(Ordering::Less);
+    }
+    if float < -LIMIT {
+        return Some(Ordering::Greater);
+    }
+    let whole = float.trunc();
+    Some(
+        int.cmp(&(whole as i64))
+            .then_with(|| 0.0.partial_cmp(&(float - whole)).unwrap_or(Ordering::Equal)),
+    )
+}
+
+fn cmp_numbers(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Int(left), Value::Int(right)) => Some(left.cmp(right)),
+        (Value::Float(left), Value::Float(right)) => left.partial_cmp(right),
+        (Value::Int(left), Value::Float(right)) => cmp_int_float(*left, *right),
+        (Value::Float(left), Value::Int(right)) => {
+            cmp_int_float(*right, *left).map(Ordering::reverse)
+        }
+        (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+            match (as_big_int(left), as_big_int(right)) {
+                (Some(left), Some(right)) => Some(left.cmp(&right)),
+                _ => cmp_numbers(
+                    &big_int_to_float(left.clone()),
+                    &big_int_to_float(right.clone()),
+                ),
+            }
+        }
+        (_, _) => None,
+    }
+}
+
+/// Pairs of vectors or maps being compared further up. Collections can contain themselves, so
+/// running into a pair again means the rest of it was already compared
+type Comparing = Vec<(*const (), *const ())>;
+
+/// Collections that are the same one or are already being compared give `same`
+fn compare_shared<T, R>(
+    left: &Rc<T>,
+    right: &Rc<T>,
+    comparing: &mut Comparing,
+    same: R,
+    compare: impl FnOnce(&mut Comparing) -> R,
+) -> R {
+    let pair = (Rc::as_ptr(left).cast(), Rc::as_ptr(right).cast());
+    if Rc::ptr_eq(left, right) || comparing.contains(&pair) {
+        return same;
+    }
+    comparing.push(pair);
+    let result = compare(comparing);
+    comparing.pop();
+    result
+}
+
+fn all_equal(left: &[Value], right: &[Value], comparing: &mut Comparing) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .all(|(left, right)| equal(left, right, comparing))
+}
+
+pub fn values_equal(left: &Value, right: &Value) -> bool {
+    equal(left, right, &mut vec![])
+}
+
+fn equal(left: &Value, right: &Value, comparing: &mut Comparing) -> bool {
+    match (left, right) {
+        (Value::Int(_) | Value::BigInt(_) | Value::Float(_), right) => {
+            cmp_numbers(left, right) == Some(Ordering::Equal)
+        }
+        (Value::String(left), Value::String(right)) => left == right,
+        (Value::Bool(left), Value::Bool(right)) => left == right,
+        (Value::Atom(left), Value::Atom(right)) => left == right,
+        (Value::Range(left), Value::Range(right)) => left.same_elements(right),
+        (Value::Null, Value::Null) | (Value::Void, Value::Void) => true,
+        (Value::Closure(left), Value::Closure(right)) => left == right,
+        (Value::Vector(left), Value::Vector(right)) => {
+            compare_shared(left, right, comparing, true, |comparing| {
+                all_equal(&left.borrow(), &right.borrow(), comparing)
+            })
+        }
+        (Value::Tuple(left), Value::Tuple(right)) => all_equal(left, right, comparing),
+        (Value::Map(left), Value::Map(right)) => {
+            compare_shared(left, right, comparing, true, |comparing| {
+                let (left, right) = (left.borrow(), right.borrow());
+                left.len() == right.len()
+                    && left.iter().all(|(key, left_value)| {
+                        right
+                            .get(key)
+                            .is_some_and(|right_value| equal(left_value, right_value, comparing))
+                    })
+            })
+        }
+        (_, _) => false,
+    }
+}
+
+fn compare_all(
+    left: &[Value],
+    right: &[Value],
+    comparing: &mut Comparing,
+) -> DashlangResult<Ordering> {
+    for (left, right) in left.iter().zip(right) {
+        let ordering = compare(left, right, comparing)?;
+        if ordering != Ordering::Equal {
+            return Ok(ordering);
+        }
+    }
+    Ok(left.len().cmp(&right.len()))
+}
+
+pub fn compare_values(left: &Value, right: &Value) -> DashlangResult<Ordering> {
+    compare(left, right, &mut vec![])
+}
+
+fn compare(left: &Value, right: &Value, comparing: &mut Comparing) -> DashlangResult<Ordering> {
+    match (left, right) {
+        (Value::String(left), Value::String(right)) => Ok(left.cmp(right)),
+        (Value::Vector(left), Value::Vector(right)) => {
+            compare_shared(left, right, comparing, Ok(Ordering::Equal), |comparing| {
+                compare_all(&left.borrow(), &right.borrow(), comparing)
+            })
+        }
+        (Value::Tuple(left), Value::Tuple(right)) => compare_all(left, right, comparing),
+        (left, right) => cmp_numbers(left, right).ok_or_else(|| {
+            DashlangError::new(
+                &format!(
+                    "Cannot compare {} with {}",
+                    left.kind_name(),
+                    right.kind_name()
+                ),
+                ErrorKind::InvalidOperation,
+            )
+        }),
+    }
+}
@@ -1,335 +1,289 @@
-use ast::{BinaryExpr, BinaryOperator, Boolean, Float, Int, Literal, Location};
+pub mod big_int;
+mod comparison;
+
+use ast::{BinaryExpr, BinaryOperator, Expr, Location};
+use big_int::{big_int_to_float, eval_big_int_operation};
+pub use comparison::{compare_values, values_equal};
 use errors::{DashlangError, DashlangResult, ErrorKind};
-use std::{cmp::Ordering, ops};
+use std::ops;
 
-use crate::{ctx::Context, eval, is_truthy, scope::Scope};
+use crate::{ctx::Context, eval, scope::Scope, value::Value};
 
-struct AritmeticLiteral(Literal);
-impl AritmeticLiteral {
-    fn get_result_location(&self, rhs: &Self) -> Location {
-        Location::new(self.0.get_location().start, rhs.0.get_location().end)
-    }
+fn int_result(value: Option<i64>, message: &str) -> DashlangResult<Value> {
+    value
+        .map(Value::Int)
+        .ok_or_else(|| DashlangError::new(message, ErrorKind::Arithmetic))
+}
+
+fn invalid_operation() -> DashlangError {
+    DashlangError::new("Invalid operation", ErrorKind::InvalidOperation)
 }
-impl ops::Add for AritmeticLiteral {
-    type Output = Result<Literal, DashlangError>;
+
+struct AritmeticValue(Value);
+impl ops::Add for AritmeticValue {
+    type Output = Result<Value, DashlangError>;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let result_location = self.get_result_location(&rhs);
         match (self.0, rhs.0) {
-            (Literal::Int(left), Literal::Int(right)) => Ok(Literal::Int(Int {
-                value: left.value + right.value,
-                location: result_location,
-            })),
-            (Literal::Float(left), Literal::Float(right)) => Ok(Literal::Float(Float {
-                value: left.value + right.value,
-                location: result_location,
-            })),
-            (Literal::Float(left), Literal::Int(right)) => Ok(Literal::Float(Float {
-                value: left.value + right.value as f64,
-                location: result_location,
-            })),
-            (Literal::Int(left), Literal::Float(right)) => Ok(Literal::Float(Float {
-                value: left.value as f64 + right.value,
-                location: result_location,
-            })),
-            (_, _) => Err(
-                DashlangError::new("Invalid operation", ErrorKind::InvalidOperation)
-                    .location(result_location),
-            ),
+            (Value::Int(left), Value::Int(right)) => {
+                int_result(left.checked_add(right), "Integer overflow")
+            }
+            (Value::Float(left), Value::Float(right)) => Ok(Value::Float(left + right)),
+            (Value::Float(left), Value::Int(right)) => Ok(Value::Float(left + right as f64)),
+            (Value::Int(left), Value::Float(right)) => Ok(Value::Float(left as f64 + right)),
+            (_, _) => Err(invalid_operation()),
         }
     }
 }
-impl ops::Sub for AritmeticLiteral {
-    type Output = Result<Literal, DashlangError>;
+impl ops::Sub for AritmeticValue {
+    type Output = Result<Value, DashlangError>;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let result_location = self.get_result_location(&rhs);
         match (self.0, rhs.0) {
-            (Literal::Int(left), Literal::Int(right)) => Ok(Literal::Int(Int {
-                value: left.value - right.value,
-                location: result_location,
-            })),
-            (Literal::Float(left), Literal::Float(right)) => Ok(Literal::Float(Float {
-                value: left.value - right.value,
-                location: result_location,
-            })),
-            (Literal::Float(left), Literal::Int(right)) => Ok(Literal::Float(Float {
-                value: left.value - right.value as f64,
-                location: result_location,
-            })),
-
-            (Literal::Int(left), Literal::Float(right)) => Ok(Literal::Float(Float {
-                value: left.value as f64 - right.value,
-                location: result_location,
-            })),
-            (_, _) => Err(
-                DashlangError::new("Invalid operation", ErrorKind::InvalidOperation)
-                    .location(result_location),
-            ),
+            (Value::Int(left), Value::Int(right)) => {
+                int_result(left.checked_sub(right), "Integer overflow")
+            }
+            (Value::Float(left), Value::Float(right)) => Ok(Value::Float(left - right)),
+            (Value::Float(left), Value::Int(right)) => Ok(Value::Float(left - right as f64)),
+            (Value::Int(left), Value::Float(right)) => Ok(Value::Float(left as f64 - right)),
+            (_, _) => Err(invalid_operation()),
         }
     }
 }
 
-impl ops::Mul for AritmeticLiteral {
-    type Output = Result<Literal, DashlangError>;
+impl ops::Mul for AritmeticValue {
+    type Output = Result<Value, DashlangError>;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let result_location = self.get_result_location(&rhs);
         match (self.0, rhs.0) {
-            (Literal::Int(left), Literal::Int(right)) => Ok(Literal::Int(Int {
-                value: left.value * right.value,
-                location: result_location,
-            })),
-            (Literal::Float(left), Literal::Float(right)) => Ok(Literal::Float(Float {
-                value: left.value * right.value,
-                location: result_location,
-            })),
-            (Literal::Float(left), Literal::Int(right)) => Ok(Literal::Float(Float {
-                value: left.value * right.value as f64,
-                location: result_location,
-            })),
-            (Literal::Int(left), Literal::Float(right)) => Ok(Literal::Float(Float {
-                value: left.value as f64 * right.value,
-                location: result_location,
-            })),
-            (_, _) => Err(
-                DashlangError::new("Invalid operation", ErrorKind::InvalidOperation)
-                    .location(result_location),
-            ),
+            (Value::Int(left), Value::Int(right)) => {
+                int_result(left.checked_mul(right), "Integer overflow")
+            }
+            (Value::Float(left), Value::Float(right)) => Ok(Value::Float(left * right)),
+            (Value::Float(left), Value::Int(right)) => Ok(Value::Float(left * right as f64)),
+            (Value::Int(left), Value::Float(right)) => Ok(Value::Float(left as f64 * right)),
+            (_, _) => Err(invalid_operation()),
         }
     }
 }
-impl ops::Div for AritmeticLiteral {
-    type Output = Result<Literal, DashlangError>;
+impl ops::Div for AritmeticValue {
+    type Output = Result<Value, DashlangError>;
 
     fn div(self, rhs: Self) -> Self::Output {
-        let result_location = self.get_result_location(&rhs);
         match (self.0, rhs.0) {
-            (Literal::Int(left), Literal::Int(right)) => Ok(Literal::Int(Int {
-                value: left.value / right.value,
-                location: result_location,
-            })),
-            (Literal::Float(left), Literal::Float(right)) => Ok(Literal::Float(Float {
-                value: left.value / right.value,
-                location: result_location,
-            })),
-            (Literal::Float(left), Literal::Int(right)) => Ok(Literal::Float(Float {
-                value: left.value / right.value as f64,
-                location: result_location,
-            })),
-            (Literal::Int(left), Literal::Float(right)) => Ok(Literal::Float(Float {
-                value: left.value as f64 / right.value,
-                location: result_location,
-            })),
-            (_, _) => Err(
-                DashlangError::new("Invalid operation", ErrorKind::InvalidOperation)
-                    .location(result_location),
+            (Value::Int(_), Value::Int(0)) => Err(DashlangError::new(
+                "Division by zero",
+                ErrorKind::Arithmetic,
+            )),
+            (Value::Int(left), Value::Int(right)) => {
+                int_result(left.checked_div(right), "Integer overflow")
+            }
+            (Value::Float(left), Value::Float(right)) => Ok(Value::Float(left / right)),
+            (Value::Float(left), Value::Int(right)) => Ok(Value::Float(left / right as f64)),
+            (Value::Int(left), Value::Float(right)) => Ok(Value::Float(left as f64 / right)),
+            (_, _) => Err(invalid_operation()),
+        }
+    }
+}
+impl ops::Rem for AritmeticValue {
+    type Output = Result<Value, DashlangError>;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        match (self.0, rhs.0) {
+            (Value::Int(_), Value::Int(0)) => Err(DashlangError::new(
+                "Division by zero",
+                ErrorKind::Arithmetic,
+            )),
+            // Only `i64::MIN % -1` overflows, and its remainder is still 0
+            (Value::Int(left), Value::Int(right)) => Ok(Value::Int(left.wrapping_rem(right))),
+            (Value::Float(left), Value::Float(right)) => Ok(Value::Float(left % right)),
+            (Value::Float(left), Value::Int(right)) => Ok(Value::Float(left % right as f64)),
+            (Value::Int(left), Value::Float(right)) => Ok(Value::Float(left as f64 % right)),
+            (_, _) => Err(invalid_operation()),
+        }
+    }
+}
+impl AritmeticValue {
+    /// A negative integer exponent gives a float
+    fn pow(self, rhs: Self) -> DashlangResult<Value> {
+        match (self.0, rhs.0) {
+            (Value::Int(left), Value::Int(right)) if right < 0 => {
+                Ok(Value::Float((left as f64).powf(right as f64)))
+            }
+            (Value::Int(left), Value::Int(right)) => int_result(
+                u32::try_from(right)
+                    .ok()
+                    .and_then(|exponent| left.checked_pow(exponent)),
+                "Integer overflow",
             ),
+            (Value::Float(left), Value::Float(right)) => Ok(Value::Float(left.powf(right))),
+            (Value::Float(left), Value::Int(right)) => Ok(Value::Float(left.powf(right as f64))),
+            (Value::Int(left), Value::Float(right)) => Ok(Value::Float((left as f64).powf(right))),
+            (_, _) => Err(invalid_operation()),
+        }
+    }
+    fn int_div(self, rhs: Self) -> DashlangResult<Value> {
+        let (left, right) = match (self.0, rhs.0) {
+            (Value::Int(left), Value::Int(right)) => {
+                return AritmeticValue(Value::Int(left)) / AritmeticValue(Value::Int(right))
+            }
+            (Value::Float(left), Value::Float(right)) => (left, right),
+            (Value::Float(left), Value::Int(right)) => (left, right as f64),
+            (Value::Int(left), Value::Float(right)) => (left as f64, right),
+            (_, _) => return Err(invalid_operation()),
+        };
+        if right == 0.0 {
+            return Err(DashlangError::new(
+                "Division by zero",
+                ErrorKind::Arithmetic,
+            ));
         }
+        let quotient = (left / right).trunc();
+        // Casting saturates, so anything that doesn't fit must be rejected before
+        let fits = quotient >= i64::MIN as f64 && quotient < i64::MAX as f64;
+        int_result(fits.then_some(quotient as i64), "Integer overflow")
     }
 }
-impl ops::BitOr for AritmeticLiteral {
-    type Output = Result<Literal, DashlangError>;
+impl ops::BitOr for AritmeticValue {
+    type Output = Result<Value, DashlangError>;
 
     fn bitor(self, rhs: Self) -> Self::Output {
-        let result_location = self.get_result_location(&rhs);
         match (self.0, rhs.0) {
-            (Literal::Int(left), Literal::Int(right)) => Ok(Literal::Int(Int {
-                value: left.value | right.value,
-                location: result_location,
-            })),
-            (_, _) => Err(DashlangError::new(
-                "Invalid operation",
-                ErrorKind::InvalidOperation,
-            )),
+            (Value::Int(left), Value::Int(right)) => Ok(Value::Int(left | right)),
+            (_, _) => Err(invalid_operation()),
         }
     }
 }
-impl ops::BitAnd for AritmeticLiteral {
-    type Output = Result<Literal, DashlangError>;
+impl ops::BitAnd for AritmeticValue {
+    type Output = Result<Value, DashlangError>;
 
     fn bitand(self, rhs: Self) -> Self::Output {
-        let result_location = self.get_result_location(&rhs);
         match (self.0, rhs.0) {
-            (Literal::Int(left), Literal::Int(right)) => Ok(Literal::Int(Int {
-                value: left.value & right.value,
-                location: result_location,
-            })),
-            (_, _) => Err(DashlangError::new(
-                "Invalid operation",
-                ErrorKind::InvalidOperation,
-            )),
+            (Value::Int(left), Value::Int(right)) => Ok(Value::Int(left & right)),
+            (_, _) => Err(invalid_operation()),
         }
     }
 }
-impl ops::Shl for AritmeticLiteral {
-    type Output = Result<Literal, DashlangError>;
+impl ops::Shl for AritmeticValue {
+    type Output = Result<Value, DashlangError>;
 
     fn shl(self, rhs: Self) -> Self::Output {
-        let result_location = self.get_result_location(&rhs);
         match (self.0, rhs.0) {
-            (Literal::Int(left), Literal::Int(right)) => Ok(Literal::Int(Int {
-                value: left.value << right.value,
-                location: result_location,
-            })),
-            (_, _) => Err(DashlangError::new(
-                "Invalid operation",
-                ErrorKind::InvalidOperation,
-            )),
+            (Value::Int(left), Value::Int(right)) => {
+                match u32::try_from(right).ok().filter(|shift| *shift < i64::BITS) {
+                    // Bits shifted out, into the sign bit too, don't come back when shifting back
+                    Some(shift) if (left << shift) >> shift == left => {
+                        Ok(Value::Int(left << shift))
+                    }
+                    Some(_) => int_result(None, "Integer overflow"),
+                    None => int_result(None, "Shift amount out of range"),
+                }
+            }
+            (_, _) => Err(invalid_operation()),
         }
     }
 }
 
-impl ops::Shr for AritmeticLiteral {
-    type Output = Result<Literal, DashlangError>;
+impl ops::Shr for AritmeticValue {
+    type Output = Result<Value, DashlangError>;
 
     fn shr(self, rhs: Self) -> Self::Output {
-        let result_location = self.get_result_location(&rhs);
         match (self.0, rhs.0) {
-            (Literal::Int(left), Literal::Int(right)) => Ok(Literal::Int(Int {
-                value: left.value >> right.value,
-                location: result_location,
-            })),
-            (_, _) => Err(DashlangError::new(
-                "Invalid operation",
-                ErrorKind::InvalidOperation,
-            )),
+            (Value::Int(left), Value::Int(right)) => int_result(
+                u32::try_from(right)
+                    .ok()
+                    .and_then(|shift| left.checked_shr(shift)),
+                "Shift amount out of range",
+            ),
+            (_, _) => Err(invalid_operation()),
         }
     }
 }
-impl ops::BitXor for AritmeticLiteral {
-    type Output = Result<Literal, DashlangError>;
+impl ops::BitXor for AritmeticValue {
+    type Output = Result<Value, DashlangError>;
 
     fn bitxor(self, rhs: Self) -> Self::Output {
-        let result_location = self.get_result_location(&rhs);
         match (self.0, rhs.0) {
-            (Literal::Int(left), Literal::Int(right)) => Ok(Literal::Int(Int {
-                value: left.value ^ right.value,
-                location: result_location,
-            })),
-            (_, _) => Err(DashlangError::new(
-                "Invalid operation",
-                ErrorKind::InvalidOperation,
-            )),
+            (Value::Int(left), Value::Int(right)) => Ok(Value::Int(left ^ right)),
+            (_, _) => Err(invalid_operation()),
         }
     }
 }
-impl PartialEq for AritmeticLiteral {
-    fn eq(&self, other: &Self) -> bool {
-        match (&self.0, &other.0) {
-            (Literal::Int(lhs), Literal::Int(rhs)) => lhs.value == rhs.value,
-            (Literal::Float(lhs), Literal::Float(rhs)) => lhs.value == rhs.value,
-            (Literal::Atom(lhs), Literal::Atom(rhs)) => lhs.value == rhs.value,
-            (Literal::Bool(lhs), Literal::Bool(rhs)) => lhs.value == rhs.value,
-            (Literal::Null(_), Literal::Null(_)) => true,
-            (_, _) => false,
+fn eval_arithmetic<T: Scope + Clone>(
+    operator: BinaryOperator,
+    lhs: Value,
+    rhs: Value,
+    ctx: &Context<T>,
+) -> DashlangResult<Value> {
+    if ctx.big_ints {
+        if let Some(result) = eval_big_int_operation(operator, &lhs, &rhs) {
+            return result;
         }
     }
-}
-impl PartialOrd for AritmeticLiteral {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match (&self.0, &other.0) {
-            (Literal::Int(lhs), Literal::Int(rhs)) => {
-                if lhs.value > rhs.value {
-                    Some(Ordering::Greater)
-                } else if lhs.value < rhs.value {
-                    return Some(Ordering::Less);
-                } else {
-                    Some(Ordering::Equal)
-                }
-            }
-            (Literal::Float(lhs), Literal::Float(rhs)) => {
-                if lhs.value > rhs.value {
-                    Some(Ordering::Greater)
-                } else if lhs.value < rhs.value {
-                    return Some(Ordering::Less);
-                } else {
-                    Some(Ordering::Equal)
-                }
-            }
-            (Literal::Float(lhs), Literal::Int(rhs)) => {
-                if lhs.value > rhs.value as f64 {
-                    Some(Ordering::Greater)
-                } else if lhs.value < rhs.value as f64 {
-                    return Some(Ordering::Less);
-                } else {
-                    Some(Ordering::Equal)
-                }
-            }
-            (Literal::Int(lhs), Literal::Float(rhs)) => {
-                if lhs.value as f64 > rhs.value {
-                    Some(Ordering::Greater)
-                } else if (lhs.value as f64) < rhs.value {
-                    return Some(Ordering::Less);
-                } else {
-                    Some(Ordering::Equal)
-                }
-            }
-            (_, _) => None,
-        }
+    let lhs = AritmeticValue(big_int_to_float(lhs));
+    let rhs = AritmeticValue(big_int_to_float(rhs));
+    match operator {
+        BinaryOperator::Add => lhs + rhs,
+        BinaryOperator::Sub => lhs - rhs,
+        BinaryOperator::Mul => lhs * rhs,
+        BinaryOperator::Div => lhs / rhs,
+        BinaryOperator::IntDiv => lhs.int_div(rhs),
+        BinaryOperator::Mod => lhs % rhs,
+        BinaryOperator::Pow => lhs.pow(rhs),
+        BinaryOperator::BitwiseOr => lhs | rhs,
+        BinaryOperator::BitwiseAnd => lhs & rhs,
+        BinaryOperator::BitwiseShiftLeft => lhs << rhs,
+        BinaryOperator::BitwiseShiftRight => lhs >> rhs,
+        BinaryOperator::BitwiseXor => lhs ^ rhs,
+        _ => Err(invalid_operation()),
     }
 }
 
+fn eval_operation<T: Scope + Clone>(
+    operator: BinaryOperator,
+    lhs: Value,
+    rhs: impl FnOnce() -> DashlangResult<Value>,
+    ctx: &Context<T>,
+) -> DashlangResult<Value> {
+    let value = match operator {
+        BinaryOperator::Gt => compare_values(&lhs, &rhs()?)?.is_gt(),
+        BinaryOperator::Ge => compare_values(&lhs, &rhs()?)?.is_ge(),
+        BinaryOperator::Lt => compare_values(&lhs, &rhs()?)?.is_lt(),
+        BinaryOperator::Le => compare_values(&lhs, &rhs()?)?.is_le(),
+        BinaryOperator::Eq => values_equal(&lhs, &rhs()?),
+        BinaryOperator::Ne => !values_equal(&lhs, &rhs()?),
+        BinaryOperator::And => lhs.is_truthy() && rhs()?.is_truthy(),
+        BinaryOperator::Or => lhs.is_truthy() || rhs()?.is_truthy(),
+        operator => return eval_arithmetic(operator, lhs, rhs()?, ctx),
+    };
+    Ok(Value::Bool(value))
+}
+
 pub fn eval_binary_expr<T: Scope + Clone>(
     op: BinaryExpr,
     ctx: &Context<T>,
-) -> DashlangResult<Literal> {
-    let arit_lhs = AritmeticLiteral(eval(op.left, ctx)?);
-    let arit_rhs = AritmeticLiteral(eval(op.right, ctx)?);
-    match op.operator {
-        BinaryOperator::Add => arit_lhs + arit_rhs,
-        BinaryOperator::Sub => arit_lhs - arit_rhs,
-        BinaryOperator::Mul => arit_lhs * arit_rhs,
-        BinaryOperator::Div => arit_lhs / arit_rhs,
-        BinaryOperator::Gt => Ok(Literal::Bool(Boolean {
-            value: arit_lhs > arit_rhs,
-            location: op.location,
-        })),
-        BinaryOperator::Eq => Ok(Literal::Bool(Boolean {
-            value: arit_lhs == arit_rhs,
-            location: op.location,
-        })),
-        BinaryOperator::Ge => Ok(Literal::Bool(Boolean {
-            value: arit_lhs >= arit_rhs,
-            location: op.location,
-        })),
-        BinaryOperator::Lt => Ok(Literal::Bool(Boolean {
-            value: arit_lhs < arit_rhs,
-            location: op.location,
-        })),
-        BinaryOperator::Le => Ok(Literal::Bool(Boolean {
-            value: arit_lhs <= arit_rhs,
-            location: op.location,
-        })),
-        BinaryOperator::And => {
-            if !is_truthy(ast::Expr::Literal(arit_lhs.0), ctx)? {
-                return Ok(Literal::Bool(Boolean {
-                    value: false,
-                    location: op.location,
-                }));
-            }
-            Ok(Literal::Bool(Boolean {
-                value: is_truthy(ast::Expr::Literal(arit_rhs.0), ctx)?,
-                location: op.location,
-            }))
-        }
-        BinaryOperator::Or => {
-            if is_truthy(ast::Expr::Literal(arit_lhs.0), ctx)? {
-                return Ok(Literal::Bool(Boolean {
-                    value: true,
-                    location: op.location,
-                }));
-            }
-            Ok(Literal::Bool(Boolean {
-                value: is_truthy(ast::Expr::Literal(arit_rhs.0), ctx)?,
-                location: op.location,
-            }))
-        }
+) -> DashlangResult<Value> {
+    let lhs = eval(op.left, ctx)?;
+    eval_with_left(op.operator, lhs, op.right, op.location, ctx)
+}
 
-        BinaryOperator::BitwiseOr => arit_lhs | arit_rhs,
-        BinaryOperator::BitwiseAnd => arit_lhs & arit_rhs,
-        BinaryOperator::BitwiseShiftLeft => arit_lhs << arit_rhs,
-        BinaryOperator::BitwiseShiftRight => arit_lhs >> arit_rhs,
-        BinaryOperator::BitwiseXor => arit_lhs ^ arit_rhs,
+/// For compound assignments, where the left operand is the value already stored
+pub fn eval_with_left<T: Scope + Clone>(
+    operator: BinaryOperator,
+    lhs: Value,
+    right: Expr,
+    location: Location,
+    ctx: &Context<T>,
+) -> DashlangResult<Value> {
+    // Only evaluated when needed, so `&&` and `||` can skip the right operand
+    let mut right_failed = false;
+    let rhs = || eval(right, ctx).inspect_err(|_| right_failed = true);
+    let result = eval_operation(operator, lhs, rhs, ctx);
+    // Errors from the right operand already point to where they happened, anything else is an
+    // error of the operation itself
+    if right_failed {
+        result
+    } else {
+        result.map_err(|err| err.location(location))
     }
 }
@@ -0,0 +1,32 @@
+use ast::{Break, Continue};
+use errors::{DashlangError, DashlangResult, ErrorKind};
+
+use crate::value::Value;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ControlFlow {
+    Normal,
+    Return(Value),
+    Break(Break),
+    Continue(Continue),
+}
+
+impl ControlFlow {
+    /// A `break` or `continue` can't escape a program or closure body
+    pub fn into_value(self) -> DashlangResult<Value> {
+        match self {
+            ControlFlow::Normal => Ok(Value::Void),
+            ControlFlow::Return(value) => Ok(value),
+            ControlFlow::Break(break_stmt) => Err(DashlangError::new(
+                "'break' used outside of a loop",
+                ErrorKind::Unknown,
+            )
+            .location(break_stmt.location)),
+            ControlFlow::Continue(continue_stmt) => Err(DashlangError::new(
+                "'continue' used outside of a loop",
+                ErrorKind::Unknown,
+            )
+            .location(continue_stmt.location)),
+        }
+    }
+}
@@ -1,29 +1,35 @@
 use std::collections::HashMap;
 
-use ast::{Literal, Program};
+use ast::Program;
 use errors::DashlangResult;
 
 use crate::{
     eval_program,
     extension::{Extension, Plugin},
     scope::Scope,
+    value::Value,
 };
 
 pub struct Context<T: Scope> {
     pub scope: T,
     pub extensions: HashMap<&'static str, Extension<T>>,
+    pub big_ints: bool,
 }
 impl<T: Scope + Clone> Context<T> {
     pub fn new(s: T) -> Self {
         Self {
             scope: s,
             extensions: HashMap::new(),
+            big_ints: false,
         }
     }
+    pub fn use_big_ints(&mut self) {
+        self.big_ints = true;
+    }
     pub fn use_extension(&mut self, extension: Extension<T>, name: &'static str) {
         self.extensions.insert(name, extension);
     }
-    pub fn run_program(&self, program: Program) -> DashlangResult<Literal> {
+    pub fn run_program(&self, program: Program) -> DashlangResult<Value> {
         eval_program(program, self)
     }
     pub fn use_plugin<P: Plugin<T>>(&mut self, plug: P) {
@@ -43,6 +49,7 @@ impl<T: Scope + Clone> Clone for Context<T> {
         Self {
             scope: self.scope.clone(),
             extensions: self.extensions.clone(),
+            big_ints: self.big_ints,
         }
     }
 }
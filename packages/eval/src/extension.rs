@@ -1,11 +1,13 @@
 use std::rc::Rc;
 
-use ast::{Call, Literal};
+use ast::Call;
 use errors::DashlangResult;
 
-use crate::{scope::Scope, Context};
+use crate::{scope::Scope, value::Value, Context};
 
-type ExtensionImplementation<S> = dyn Fn(&Context<S>, Call) -> DashlangResult<Literal>;
+/// Extensions always evaluate to a value: any closure body they run should go through
+/// `eval_program`, so `break`/`continue` can't leak out of them
+type ExtensionImplementation<S> = dyn Fn(&Context<S>, Call) -> DashlangResult<Value>;
 #[derive(Clone)]
 pub struct Extension<S: Scope> {
     pub implementation: Rc<ExtensionImplementation<S>>,
@@ -0,0 +1,39 @@
+use ast::Location;
+use errors::{DashlangError, DashlangResult, ErrorKind};
+
+use crate::value::Value;
+
+/// Vectors are copied first, so changing one while looping over it doesn't change the iteration
+pub fn iterate(
+    value: Value,
+    location: Location,
+) -> DashlangResult<Box<dyn Iterator<Item = Value>>> {
+    match value {
+        Value::Vector(vector) => Ok(Box::new(vector.borrow().clone().into_iter())),
+        Value::Tuple(items) => Ok(Box::new(items.into_iter())),
+        Value::String(string) => Ok(Box::new(
+            string
+                .chars()
+                .map(|char| Value::String(char.to_string()))
+                .collect::<Vec<_>>()
+                .into_iter(),
+        )),
+        Value::Map(map) => {
+            let mut entries: Vec<(String, Value)> = map
+                .borrow()
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+            entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+            Ok(Box::new(entries.into_iter().map(|(key, value)| {
+                Value::Tuple(vec![Value::String(key), value])
+            })))
+        }
+        Value::Range(range) => Ok(Box::new(range.iter().map(Value::Int))),
+        value => Err(DashlangError::new(
+            &format!("Cannot iterate over {}", value.kind_name()),
+            ErrorKind::InvalidOperation,
+        )
+        .location(location)),
+    }
+}
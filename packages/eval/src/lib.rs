@@ -1,104 +1,163 @@
+pub mod access;
 pub mod binary_expr;
+pub mod control_flow;
 pub mod ctx;
 pub mod extension;
+pub mod iteration;
+pub mod pattern;
 pub mod scope;
 pub mod stdlib;
 #[cfg(test)]
 mod tests;
+pub mod value;
 
-use std::cmp::Ordering;
+use std::{cmp::Ordering, rc::Rc};
 
 use ast::{
-    Boolean, Call, DestructuringAsignment, Expr, Int, Literal, Program, Stmt, Tuple, UnaryExpr,
-    Void,
+    AssignmentExpr, Call, DestructuringAsignment, Expr, ForIn, If, Literal, Location, Program,
+    Range, Stmt, StringInterpolation, UnaryExpr,
 };
 
-use binary_expr::eval_binary_expr;
+use access::{assign_path, eval_access, read_path};
+use binary_expr::{big_int::big_int_value, eval_binary_expr, eval_with_left};
+use control_flow::ControlFlow;
 use ctx::Context;
 use errors::{DashlangError, DashlangResult, ErrorKind};
 use extension::{Extension, Plugin};
+use iteration::iterate;
+use num_bigint::BigInt;
+use pattern::{bind_pattern, eval_match};
 use scope::Scope;
+use stdlib::stdio::literal_display::stdlib_literal_display;
+use value::{Closure, Environment, Value};
 
 fn is_truthy<T: Scope + Clone>(expr: Expr, scope: &Context<T>) -> DashlangResult<bool> {
-    match expr {
-        Expr::Literal(value) => match value {
-            Literal::Closure(_) => Ok(true),
-            Literal::Int(num) => Ok(num.value != 0),
-            Literal::Float(num) => Ok(num.value != 0.0),
-            Literal::String(string) => Ok(!string.value.is_empty()),
-            Literal::Vector(val) => Ok(!val.value.is_empty()),
-            Literal::Bool(val) => Ok(val.value),
-            Literal::Null(_) => Ok(false),
-            Literal::Void(_) => Ok(false),
-            Literal::Tuple(_) => Ok(false),
-            Literal::Map(map) => Ok(!map.value.is_empty()),
-            Literal::Atom(_) => Ok(true),
-        },
-        expr => is_truthy(Expr::Literal(eval(expr, scope)?), scope),
-    }
+    Ok(eval(expr, scope)?.is_truthy())
 }
 
-fn eval_unary_op<T: Scope + Clone>(op: UnaryExpr, ctx: &Context<T>) -> DashlangResult<Literal> {
+fn eval_unary_op<T: Scope + Clone>(op: UnaryExpr, ctx: &Context<T>) -> DashlangResult<Value> {
     match op.operator {
-        ast::UnaryOperator::Not => Ok(Literal::Bool(Boolean {
-            value: !is_truthy(op.operand, ctx)?,
-            location: op.location,
-        })),
-        ast::UnaryOperator::BitwiseNot => Ok(Literal::Int(Int {
-            value: match eval(op.operand, ctx)? {
-                Literal::Int(integer) => !integer.value,
-                _ => Err(DashlangError::new(
-                    "Expected integer",
-                    ErrorKind::InvalidOperation,
-                ))?,
-            },
-            location: op.location,
-        })),
+        ast::UnaryOperator::Not => Ok(Value::Bool(!is_truthy(op.operand, ctx)?)),
+        ast::UnaryOperator::BitwiseNot => match eval(op.operand, ctx)? {
+            Value::Int(integer) => Ok(Value::Int(!integer)),
+            Value::BigInt(integer) => Ok(big_int_value(!integer)),
+            _ => Err(
+                DashlangError::new("Expected integer", ErrorKind::InvalidOperation)
+                    .location(op.location),
+            ),
+        },
+        ast::UnaryOperator::Neg => match eval(op.operand, ctx)? {
+            Value::Int(integer) if ctx.big_ints => Ok(big_int_value(-BigInt::from(integer))),
+            Value::Int(integer) => integer.checked_neg().map(Value::Int).ok_or_else(|| {
+                DashlangError::new("Integer overflow", ErrorKind::Arithmetic).location(op.location)
+            }),
+            Value::BigInt(integer) => Ok(big_int_value(-integer)),
+            Value::Float(float) => Ok(Value::Float(-float)),
+            _ => Err(
+                DashlangError::new("Expected a number", ErrorKind::InvalidOperation)
+                    .location(op.location),
+            ),
+        },
     }
 }
 
-pub fn eval_program<T: Scope + Clone>(
+/// Unlabeled jumps always target the innermost loop
+fn targets_loop(jump_label: &Option<String>, loop_label: &Option<String>) -> bool {
+    jump_label.is_none() || jump_label == loop_label
+}
+
+pub fn eval_block<T: Scope + Clone>(
     program: Program,
     ctx: &Context<T>,
-) -> DashlangResult<Literal> {
+) -> DashlangResult<ControlFlow> {
     for stmt in program {
         match stmt {
             Stmt::Return(val) => {
-                return eval(val.value, ctx);
+                return Ok(ControlFlow::Return(eval(val.value, ctx)?));
             }
             Stmt::If(if_stmt) => {
-                if is_truthy(if_stmt.cond, ctx)? {
-                    let block_result = eval_program(if_stmt.body, ctx)?;
-                    match block_result {
-                        Literal::Void(_) => (),
-                        val => return Ok(val),
-                    }
-                } else if let Some(else_block) = if_stmt.else_block {
-                    let block_result = eval_program(else_block, ctx)?;
-                    match block_result {
-                        Literal::Null(_) => (),
-                        val => return Ok(val),
+                let branch = if is_truthy(if_stmt.cond, ctx)? {
+                    Some(if_stmt.body)
+                } else {
+                    if_stmt.else_block
+                };
+                if let Some(branch) = branch {
+                    match eval_block(branch, ctx)? {
+                        ControlFlow::Normal => (),
+                        flow => return Ok(flow),
                     }
                 }
             }
             Stmt::While(while_stmt) => {
+                let loop_label = &while_stmt.label;
                 while is_truthy(while_stmt.clone().cond, ctx)? {
-                    let block_result = eval_program(while_stmt.clone().body, ctx)?;
-                    match block_result {
-                        Literal::Void(_) => (),
-                        val => return Ok(val),
+                    match eval_block(while_stmt.clone().body, ctx)? {
+                        ControlFlow::Normal => (),
+                        ControlFlow::Break(jump) if targets_loop(&jump.label, loop_label) => break,
+                        ControlFlow::Continue(jump) if targets_loop(&jump.label, loop_label) => {
+                            continue
+                        }
+                        flow => return Ok(flow),
                     }
                 }
             }
             Stmt::For(for_stmt) => {
-                eval_program(vec![for_stmt.clone().init], ctx)?;
+                match eval_block(vec![for_stmt.clone().init], ctx)? {
+                    ControlFlow::Normal => (),
+                    flow => return Ok(flow),
+                }
+                let loop_label = &for_stmt.label;
                 while is_truthy(for_stmt.clone().cond, ctx)? {
-                    let block_result = eval_program(for_stmt.clone().body, ctx)?;
-                    match block_result {
-                        Literal::Void(_) => (),
-                        val => return Ok(val),
+                    match eval_block(for_stmt.clone().body, ctx)? {
+                        ControlFlow::Normal => (),
+                        ControlFlow::Break(jump) if targets_loop(&jump.label, loop_label) => break,
+                        // `continue` skips the rest of the body, but still runs the iteration
+                        ControlFlow::Continue(jump) if targets_loop(&jump.label, loop_label) => (),
+                        flow => return Ok(flow),
+                    }
+                    match eval_block(vec![for_stmt.clone().iteration], ctx)? {
+                        ControlFlow::Normal => (),
+                        flow => return Ok(flow),
+                    }
+                }
+            }
+            Stmt::ForIn(for_in) => {
+                let ForIn {
+                    label,
+                    pattern,
+                    iterable,
+                    body,
+                    ..
+                } = *for_in;
+                let iterable_location = iterable.get_location();
+                for item in iterate(eval(iterable, ctx)?, iterable_location)? {
+                    bind_pattern(&pattern, &item, ctx)?;
+                    match eval_block(body.clone(), ctx)? {
+                        ControlFlow::Normal => (),
+                        ControlFlow::Break(jump) if targets_loop(&jump.label, &label) => break,
+                        ControlFlow::Continue(jump) if targets_loop(&jump.label, &label) => {
+                            continue
+                        }
+                        flow => return Ok(flow),
                     }
-                    eval_program(vec![for_stmt.clone().iteration], ctx)?;
+                }
+            }
+            Stmt::Break(break_stmt) => return Ok(ControlFlow::Break(break_stmt)),
+            Stmt::Continue(continue_stmt) => return Ok(ControlFlow::Continue(continue_stmt)),
+            Stmt::Nonlocal(nonlocal) => {
+                for symbol in nonlocal.symbols {
+                    if !ctx.scope.declare_nonlocal(&symbol) {
+                        return Err(DashlangError::new(
+                            &format!("No binding for nonlocal '{symbol}' found"),
+                            ErrorKind::Unknown,
+                        )
+                        .location(nonlocal.location));
+                    }
+                }
+            }
+            Stmt::Global(global) => {
+                for symbol in global.symbols {
+                    ctx.scope.declare_global(&symbol);
                 }
             }
             Stmt::Expr(expr) => {
@@ -106,103 +165,287 @@ pub fn eval_program<T: Scope + Clone>(
             }
         }
     }
-    Ok(Literal::Void(Void {
-        location: Default::default(),
-    }))
+    Ok(ControlFlow::Normal)
 }
 
-fn eval_call<T: Scope + Clone>(call: Call, ctx: &Context<T>) -> DashlangResult<Literal> {
-    if let Some(found_extension) = ctx.get_extension(&call.symbol) {
-        let local_context = ctx.clone();
-        return (found_extension.implementation)(&local_context, call);
+/// Jumps can't leave a block used as a value, since the expression around it would be left without
+/// a value
+fn eval_value_block<T: Scope + Clone>(
+    mut body: Program,
+    location: Location,
+    ctx: &Context<T>,
+) -> DashlangResult<Value> {
+    let last = match body.last() {
+        Some(Stmt::Expr(_) | Stmt::If(_)) => body.pop(),
+        _ => None,
+    };
+    let (keyword, jump_location) = match eval_block(body, ctx)? {
+        ControlFlow::Normal => match last {
+            Some(Stmt::Expr(expr)) => return eval(expr, ctx),
+            Some(Stmt::If(if_expr)) => return eval_if(if_expr, ctx),
+            _ => return Ok(Value::Void),
+        },
+        ControlFlow::Return(_) => ("return", location),
+        ControlFlow::Break(break_stmt) => ("break", break_stmt.location),
+        ControlFlow::Continue(continue_stmt) => ("continue", continue_stmt.location),
+    };
+    Err(DashlangError::new(
+        &format!("'{keyword}' can't jump out of a block used as a value"),
+        ErrorKind::Unknown,
+    )
+    .location(jump_location))
+}
+
+fn eval_if<T: Scope + Clone>(if_expr: If, ctx: &Context<T>) -> DashlangResult<Value> {
+    let branch = if is_truthy(if_expr.cond, ctx)? {
+        Some(if_expr.body)
+    } else {
+        if_expr.else_block
+    };
+    match branch {
+        Some(branch) => eval_value_block(branch, if_expr.location, ctx),
+        None => Ok(Value::Void),
     }
-    if let Literal::Closure(closure) = ctx.scope.get(&call.symbol) {
+}
+
+pub fn eval_program<T: Scope + Clone>(program: Program, ctx: &Context<T>) -> DashlangResult<Value> {
+    eval_block(program, ctx)?.into_value()
+}
+
+fn describe_callee(callee: &Expr) -> String {
+    match callee {
+        Expr::Symbol(symbol) => format!("'{}'", symbol.value),
+        _ => String::from("expression"),
+    }
+}
+
+fn eval_call<T: Scope + Clone>(call: Call, ctx: &Context<T>) -> DashlangResult<Value> {
+    if let Expr::Symbol(symbol) = call.callee.as_ref() {
+        if let Some(found_extension) = ctx.get_extension(&symbol.value) {
+            let local_context = ctx.clone();
+            return (found_extension.implementation)(&local_context, call);
+        }
+    }
+    let callee_name = describe_callee(&call.callee);
+    let callee_location = call.callee.get_location();
+    if let Value::Closure(closure) = eval(*call.callee, ctx)? {
         match closure.params.len().cmp(&call.args.len()) {
             Ordering::Less | Ordering::Greater => {
                 return Err(DashlangError::new(
                     &format!(
-                    "Could not evaluate '{}'. Expected {} argument{s}, but {} {s1} given instead",
-                    call.symbol,
-                    closure.params.len(),
-                    call.args.len(),
-                    s = if closure.params.len() > 1_usize {"s"} else {""},
-                    s1 = if call.args.len() > 1 {"were"} else {"was"}
-                ),
+                        "Could not evaluate {}. Expected {} argument{s}, but {} {s1} given instead",
+                        callee_name,
+                        closure.params.len(),
+                        call.args.len(),
+                        s = if closure.params.len() > 1_usize {
+                            "s"
+                        } else {
+                            ""
+                        },
+                        s1 = if call.args.len() > 1 { "were" } else { "was" }
+                    ),
                     ErrorKind::WrongArgs,
                 )
                 .location(call.location))
             }
             Ordering::Equal => {
-                let local_context = ctx.clone();
-                let args: Result<Vec<Literal>, DashlangError> = call
-                    .args
-                    .into_iter()
-                    .map(|expr| eval(expr, &local_context))
-                    .collect();
+                let args: Result<Vec<Value>, DashlangError> =
+                    call.args.into_iter().map(|expr| eval(expr, ctx)).collect();
+                // The body runs in a child of the scope the closure was defined in, so free
+                // variables resolve lexically instead of to whatever the caller has in scope
+                let Some(env) = closure.env.0.downcast_ref::<T>() else {
+                    // Falling back to the caller's scope would silently turn into dynamic scoping
+                    return Err(DashlangError::new(
+                        &format!(
+                            "Cannot call {callee_name}: it was created with another kind of scope"
+                        ),
+                        ErrorKind::Unknown,
+                    )
+                    .location(call.location));
+                };
+                let local_context = Context {
+                    scope: env.clone(),
+                    extensions: ctx.extensions.clone(),
+                    big_ints: ctx.big_ints,
+                };
                 match args {
                     Ok(ok_args) => {
-                        for (symbol, val) in closure.params.iter().zip(ok_args) {
+                        for (param, val) in closure.params.iter().zip(ok_args) {
                             // Inject all arguments into local scope
-                            local_context.scope.set(symbol, val);
+                            bind_pattern(param, &val, &local_context)?;
                         }
                     }
                     Err(args_err) => return Err(args_err),
                 }
-                return eval_program(closure.body, &local_context);
+                return eval_block(closure.body, &local_context)?.into_value();
             }
         }
     }
     Err(DashlangError::new(
-        &format!("Cannot call '{}': not callable", call.symbol),
+        &format!("Cannot call {callee_name}: not callable"),
         ErrorKind::NonCallable,
     )
-    .location(call.location))
+    .location(callee_location))
 }
 
 fn eval_destructuring_assign_expr<T: Scope + Clone>(
     expr: DestructuringAsignment,
     ctx: &Context<T>,
-) -> DashlangResult<Literal> {
+) -> DashlangResult<Value> {
     let value = eval(*expr.value, ctx)?;
-    if let Literal::Tuple(tup) = value {
-        let mut eval_expressions: Vec<Expr> = vec![];
-        if expr.symbols.len() != tup.value.len() {
-            return Err(DashlangError::new(
-                "Number os elements in tuples don't match",
-                ErrorKind::WrongArgs,
+    bind_pattern(&expr.pattern, &value, ctx)?;
+    Ok(value)
+}
+
+fn eval_range<T: Scope + Clone>(range: Range, ctx: &Context<T>) -> DashlangResult<Value> {
+    let expect_int = |expr: Expr| {
+        let location = expr.get_location();
+        match eval(expr, ctx)? {
+            Value::Int(value) => Ok(value),
+            _ => Err(DashlangError::new(
+                "Expected range bounds and step to be integers",
+                ErrorKind::InvalidOperation,
             )
-            .location(expr.location));
+            .location(location)),
         }
-        for (symbol, expr) in expr.symbols.into_iter().zip(tup.value) {
-            let evaluated_expr = eval(expr, ctx)?;
-            eval_expressions.push(Expr::Literal(evaluated_expr.clone()));
-            ctx.scope.set(&symbol.value, evaluated_expr);
+    };
+    let start = expect_int(range.start)?;
+    let end = expect_int(range.end)?;
+    let step = match range.step {
+        Some(step) => {
+            let step_location = step.get_location();
+            match expect_int(step)? {
+                0 => {
+                    return Err(DashlangError::new(
+                        "Range step can't be zero",
+                        ErrorKind::InvalidOperation,
+                    )
+                    .location(step_location))
+                }
+                step => step,
+            }
         }
-        Ok(Literal::Tuple(Tuple {
-            value: eval_expressions,
-            location: expr.location,
-        }))
-    } else {
-        Err(DashlangError::new(
-            "Expected value to be a tuple",
-            ErrorKind::InvalidOperation,
-        ))
+        None => 1,
+    };
+    let value = value::Range::new(start, end, step, range.inclusive).ok_or_else(|| {
+        DashlangError::new("Range is too long", ErrorKind::Arithmetic).location(range.location)
+    })?;
+    Ok(Value::Range(value))
+}
+
+fn eval_assignment<T: Scope + Clone>(
+    assign: AssignmentExpr,
+    ctx: &Context<T>,
+) -> DashlangResult<Value> {
+    let Some(operator) = assign.operator else {
+        let evaluated = eval(*assign.value, ctx)?;
+        let path = eval_path(assign.path, ctx)?;
+        assign_value(
+            &assign.symbol,
+            &path,
+            evaluated.clone(),
+            assign.location,
+            ctx,
+        )?;
+        return Ok(evaluated);
+    };
+    // The keys are evaluated once, so the same element is read and written
+    let path = eval_path(assign.path, ctx)?;
+    let current = read_path(ctx.scope.get(&assign.symbol), &path, assign.location)?;
+    let evaluated = eval_with_left(operator, current, *assign.value, assign.location, ctx)?;
+    assign_value(
+        &assign.symbol,
+        &path,
+        evaluated.clone(),
+        assign.location,
+        ctx,
+    )?;
+    Ok(evaluated)
+}
+
+fn eval_path<T: Scope + Clone>(
+    path: Vec<Expr>,
+    ctx: &Context<T>,
+) -> DashlangResult<Vec<(Value, Location)>> {
+    path.into_iter()
+        .map(|key| {
+            let key_location = key.get_location();
+            Ok((eval(key, ctx)?, key_location))
+        })
+        .collect()
+}
+
+fn assign_value<T: Scope + Clone>(
+    symbol: &str,
+    path: &[(Value, Location)],
+    value: Value,
+    location: Location,
+    ctx: &Context<T>,
+) -> DashlangResult<()> {
+    if path.is_empty() {
+        ctx.scope.set(symbol, value);
+        return Ok(());
+    }
+    assign_path(ctx.scope.get(symbol), path, value, location)
+}
+
+fn eval_string_interpolation<T: Scope + Clone>(
+    interpolation: StringInterpolation,
+    ctx: &Context<T>,
+) -> DashlangResult<Value> {
+    let mut value = String::new();
+    for part in interpolation.parts {
+        value.push_str(&stdlib_literal_display(&eval(part, ctx)?));
     }
+    Ok(Value::String(value))
+}
+
+fn eval_all<T: Scope + Clone>(exprs: Vec<Expr>, ctx: &Context<T>) -> DashlangResult<Vec<Value>> {
+    exprs.into_iter().map(|expr| eval(expr, ctx)).collect()
 }
 
-pub fn eval<T: Scope + Clone>(expr: Expr, ctx: &Context<T>) -> DashlangResult<Literal> {
+/// Elements of collections get evaluated here, once, and closures capture the current scope
+fn eval_literal<T: Scope + Clone>(literal: Literal, ctx: &Context<T>) -> DashlangResult<Value> {
+    let value = match literal {
+        Literal::Closure(closure) => Value::Closure(Closure {
+            params: closure.params,
+            body: closure.body,
+            env: Environment(Rc::new(ctx.scope.clone())),
+        }),
+        Literal::Int(int) => Value::Int(int.value),
+        Literal::Float(float) => Value::Float(float.value),
+        Literal::String(string) => Value::String(string.value),
+        Literal::Bool(boolean) => Value::Bool(boolean.value),
+        Literal::Vector(vector) => Value::vector(eval_all(vector.value, ctx)?),
+        Literal::Null(_) => Value::Null,
+        Literal::Void(_) => Value::Void,
+        Literal::Tuple(tuple) => Value::Tuple(eval_all(tuple.value, ctx)?),
+        Literal::Map(map) => Value::map(
+            map.value
+                .into_iter()
+                .map(|(key, expr)| Ok((key, eval(expr, ctx)?)))
+                .collect::<DashlangResult<_>>()?,
+        ),
+        Literal::Atom(atom) => Value::Atom(atom.value),
+    };
+    Ok(value)
+}
+
+pub fn eval<T: Scope + Clone>(expr: Expr, ctx: &Context<T>) -> DashlangResult<Value> {
     match expr {
-        Expr::Literal(val) => Ok(val),
+        Expr::Literal(val) => eval_literal(val, ctx),
         Expr::BinaryExpr(op) => eval_binary_expr(*op, ctx),
-        Expr::Assignment(assign) => {
-            let evaluated = eval(*assign.value, ctx)?;
-            ctx.scope.set(&assign.symbol, evaluated.clone());
-            Ok(evaluated)
-        }
+        Expr::Assignment(assign) => eval_assignment(assign, ctx),
         Expr::Call(call) => eval_call(call, ctx),
         Expr::Symbol(symbol) => Ok(ctx.scope.get(&symbol.value)),
         Expr::UnaryExpr(op) => eval_unary_op(*op, ctx),
         Expr::SubExpr(sub) => eval(*sub.value, ctx),
         Expr::DestructuringAsignment(dest) => eval_destructuring_assign_expr(dest, ctx),
+        Expr::StringInterpolation(interpolation) => eval_string_interpolation(interpolation, ctx),
+        Expr::Access(access) => eval_access(*access, ctx),
+        Expr::Range(range) => eval_range(*range, ctx),
+        Expr::Match(match_expr) => eval_match(*match_expr, ctx),
+        Expr::If(if_expr) => eval_if(*if_expr, ctx),
+        Expr::Block(block) => eval_value_block(block.body, block.location, ctx),
     }
 }
@@ -0,0 +1,120 @@
+use ast::{Match, Pattern};
+use errors::{DashlangError, DashlangResult, ErrorKind};
+
+use crate::{
+    binary_expr::values_equal, ctx::Context, eval, eval_literal, scope::Scope,
+    stdlib::stdio::literal_display::stdlib_literal_display, value::Value,
+};
+
+/// Nothing is bound until the whole pattern fits, so an arm that doesn't match leaves the scope
+/// alone
+pub fn match_pattern<T: Scope + Clone>(
+    pattern: &Pattern,
+    value: &Value,
+    bindings: &mut Vec<(String, Value)>,
+    ctx: &Context<T>,
+) -> DashlangResult<bool> {
+    let match_all = |patterns: &[Pattern], values: &[Value], bindings: &mut Vec<_>| {
+        for (pattern, value) in patterns.iter().zip(values) {
+            if !match_pattern(pattern, value, bindings, ctx)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    };
+    match (pattern, value) {
+        (Pattern::Wildcard(_), _) => Ok(true),
+        (Pattern::Binding(symbol), value) => {
+            bindings.push((symbol.value.clone(), value.clone()));
+            Ok(true)
+        }
+        (Pattern::Literal(literal), value) => {
+            Ok(values_equal(&eval_literal(literal.clone(), ctx)?, value))
+        }
+        (Pattern::Tuple(tuple), Value::Tuple(items)) => {
+            Ok(tuple.items.len() == items.len() && match_all(&tuple.items, items, bindings)?)
+        }
+        (Pattern::Vector(vector), Value::Vector(items)) => {
+            let items = items.borrow();
+            let fits = match vector.rest {
+                Some(_) => items.len() >= vector.items.len(),
+                None => items.len() == vector.items.len(),
+            };
+            if !fits || !match_all(&vector.items, &items, bindings)? {
+                return Ok(false);
+            }
+            if let Some(symbol) = vector.rest.as_ref().and_then(|rest| rest.symbol.as_ref()) {
+                let rest = items[vector.items.len()..].to_vec();
+                bindings.push((symbol.value.clone(), Value::vector(rest)));
+            }
+            Ok(true)
+        }
+        (Pattern::Map(map_pattern), Value::Map(map)) => {
+            let map = map.borrow();
+            for (key, pattern) in &map_pattern.fields {
+                match map.get(key) {
+                    Some(value) if match_pattern(pattern, value, bindings, ctx)? => (),
+                    _ => return Ok(false),
+                }
+            }
+            Ok(true)
+        }
+        (_, _) => Ok(false),
+    }
+}
+
+/// Unlike in a `match`, a value that doesn't fit is an error
+pub fn bind_pattern<T: Scope + Clone>(
+    pattern: &Pattern,
+    value: &Value,
+    ctx: &Context<T>,
+) -> DashlangResult<()> {
+    let mut bindings = vec![];
+    if !match_pattern(pattern, value, &mut bindings, ctx)? {
+        return Err(DashlangError::new(
+            &format!(
+                "Cannot destructure {} with this pattern",
+                stdlib_literal_display(value)
+            ),
+            ErrorKind::PatternMismatch,
+        )
+        .location(pattern.get_location()));
+    }
+    for (symbol, bound) in bindings {
+        ctx.scope.set(&symbol, bound);
+    }
+    Ok(())
+}
+
+pub fn eval_match<T: Scope + Clone>(match_expr: Match, ctx: &Context<T>) -> DashlangResult<Value> {
+    let value_location = match_expr.value.get_location();
+    let value = eval(match_expr.value, ctx)?;
+    for arm in match_expr.arms {
+        let mut bindings = vec![];
+        if !match_pattern(&arm.pattern, &value, &mut bindings, ctx)? {
+            continue;
+        }
+        // The guard sees the arm's bindings without them leaking out when it fails
+        if let Some(guard) = arm.guard {
+            let guard_ctx = ctx.clone();
+            for (symbol, bound) in &bindings {
+                guard_ctx.scope.set(symbol, bound.clone());
+            }
+            if !eval(guard, &guard_ctx)?.is_truthy() {
+                continue;
+            }
+        }
+        for (symbol, bound) in bindings {
+            ctx.scope.set(&symbol, bound);
+        }
+        return eval(arm.value, ctx);
+    }
+    Err(DashlangError::new(
+        &format!(
+            "Non-exhaustive match: no arm matches {}",
+            stdlib_literal_display(&value)
+        ),
+        ErrorKind::NoMatch,
+    )
+    .location(value_location))
+}
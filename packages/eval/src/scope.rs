@@ -1,38 +1,118 @@
-use ast::{Literal, Void};
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-pub trait Scope {
-    fn get(&self, symbol: &str) -> Literal;
-    fn set(&self, symbol: &str, val: Literal);
+use crate::value::Value;
+
+/// Scopes are `'static` so closures can hold on to the scope they were defined in
+pub trait Scope: 'static {
+    fn get(&self, symbol: &str) -> Value;
+    /// Binds `symbol` in this scope, unless it was declared `nonlocal` or `global` here
+    fn set(&self, symbol: &str, val: Value);
+    /// Makes `set` update the nearest binding of `symbol` in an enclosing scope. Returns `false`
+    /// when no enclosing scope has it
+    fn declare_nonlocal(&self, symbol: &str) -> bool;
+    fn declare_global(&self, symbol: &str);
+}
+#[derive(Clone, Copy)]
+enum Declaration {
+    Nonlocal,
+    Global,
 }
 #[derive(Default)]
 pub struct HashScope {
-    memory: Rc<RefCell<HashMap<String, Literal>>>,
-    parent: Option<Rc<dyn Scope>>,
+    memory: Rc<RefCell<HashMap<String, Value>>>,
+    declarations: Rc<RefCell<HashMap<String, Declaration>>>,
+    parent: Option<Rc<HashScope>>,
+}
+impl HashScope {
+    fn root(&self) -> &HashScope {
+        match &self.parent {
+            Some(parent) => parent.root(),
+            None => self,
+        }
+    }
+    fn has(&self, symbol: &str) -> bool {
+        self.memory.borrow().contains_key(symbol)
+            || self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.has(symbol))
+    }
+    fn update(&self, symbol: &str, val: Value) -> bool {
+        let declaration = self.declarations.borrow().get(symbol).copied();
+        match declaration {
+            Some(_) => {
+                self.set(symbol, val);
+                true
+            }
+            None if self.memory.borrow().contains_key(symbol) => {
+                self.memory.borrow_mut().insert(symbol.to_owned(), val);
+                true
+            }
+            None => self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.update(symbol, val)),
+        }
+    }
 }
 impl Scope for HashScope {
-    fn get(&self, symbol: &str) -> Literal {
+    fn get(&self, symbol: &str) -> Value {
+        if let Some(Declaration::Global) = self.declarations.borrow().get(symbol) {
+            return self.root().get(symbol);
+        }
         match self.memory.borrow().get(symbol) {
             Some(value) => value.clone(),
             None => match &self.parent {
                 Some(parent) => parent.get(symbol),
-                None => Literal::Void(Void {
-                    location: Default::default(),
-                }),
+                None => Value::Void,
             },
         }
     }
 
-    fn set(&self, symbol: &str, val: Literal) {
-        self.memory.borrow_mut().insert(symbol.to_owned(), val);
+    fn set(&self, symbol: &str, val: Value) {
+        let declaration = self.declarations.borrow().get(symbol).copied();
+        match (declaration, &self.parent) {
+            (Some(Declaration::Nonlocal), Some(parent)) => {
+                // Declaring the symbol made sure some enclosing scope has it
+                parent.update(symbol, val);
+            }
+            (Some(Declaration::Global), Some(_)) => self.root().set(symbol, val),
+            _ => {
+                self.memory.borrow_mut().insert(symbol.to_owned(), val);
+            }
+        }
+    }
+
+    fn declare_nonlocal(&self, symbol: &str) -> bool {
+        match &self.parent {
+            Some(parent) if parent.has(symbol) => {
+                self.memory.borrow_mut().remove(symbol);
+                self.declarations
+                    .borrow_mut()
+                    .insert(symbol.to_owned(), Declaration::Nonlocal);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn declare_global(&self, symbol: &str) {
+        if self.parent.is_some() {
+            self.memory.borrow_mut().remove(symbol);
+            self.declarations
+                .borrow_mut()
+                .insert(symbol.to_owned(), Declaration::Global);
+        }
     }
 }
 impl Clone for HashScope {
     fn clone(&self) -> Self {
         Self {
             memory: Rc::new(RefCell::new(HashMap::new())),
+            declarations: Rc::new(RefCell::new(HashMap::new())),
             parent: Some(Rc::new(Self {
                 memory: self.memory.clone(),
+                declarations: self.declarations.clone(),
                 parent: self.parent.clone(),
             })),
         }
@@ -41,62 +121,67 @@ impl Clone for HashScope {
 
 #[cfg(test)]
 mod tests {
-    use ast::Str;
-
     use super::*;
 
+    fn int(value: i64) -> Value {
+        Value::Int(value)
+    }
+
     #[test]
     fn test_allocate() {
         let scope = HashScope::default();
-        scope.set(
-            "name",
-            Literal::String(Str {
-                value: String::from("John Doe"),
-                location: Default::default(),
-            }),
-        );
+        scope.set("name", Value::String(String::from("John Doe")));
 
-        assert_eq!(
-            scope.get("name"),
-            Literal::String(Str {
-                value: String::from("John Doe"),
-                location: Default::default()
-            })
-        );
+        assert_eq!(scope.get("name"), Value::String(String::from("John Doe")));
     }
     #[test]
     fn test_child_scope() {
         let global = HashScope::default();
-        global.set(
-            "name",
-            Literal::String(Str {
-                value: String::from("John Doe"),
-                location: Default::default(),
-            }),
-        );
+        global.set("name", Value::String(String::from("John Doe")));
 
         let local = global.clone();
 
+        assert_eq!(local.get("name"), Value::String(String::from("John Doe")));
+        local.set("name", Value::String(String::from("John Doe jr.")));
         assert_eq!(
             local.get("name"),
-            Literal::String(Str {
-                value: String::from("John Doe"),
-                location: Default::default()
-            })
-        );
-        local.set(
-            "name",
-            Literal::String(Str {
-                value: String::from("John Doe jr."),
-                location: Default::default(),
-            }),
-        );
-        assert_eq!(
-            local.get("name"),
-            Literal::String(Str {
-                value: String::from("John Doe jr."),
-                location: Default::default()
-            })
+            Value::String(String::from("John Doe jr."))
         );
     }
+    #[test]
+    fn test_assignment_shadows_outer_binding() {
+        let global = HashScope::default();
+        global.set("count", int(1));
+        let local = global.clone();
+        local.set("count", int(2));
+        assert_eq!(local.get("count"), int(2));
+        assert_eq!(global.get("count"), int(1));
+    }
+    #[test]
+    fn test_nonlocal_updates_nearest_binding() {
+        let global = HashScope::default();
+        global.set("count", int(0));
+        let outer = global.clone();
+        outer.set("count", int(10));
+        let inner = outer.clone();
+        assert!(inner.declare_nonlocal("count"));
+        inner.set("count", int(11));
+        assert_eq!(inner.get("count"), int(11));
+        assert_eq!(outer.get("count"), int(11));
+        assert_eq!(global.get("count"), int(0));
+        assert!(!inner.declare_nonlocal("missing"));
+    }
+    #[test]
+    fn test_global_updates_outermost_scope() {
+        let global = HashScope::default();
+        global.set("total", int(0));
+        let outer = global.clone();
+        outer.set("total", int(10));
+        let inner = outer.clone();
+        inner.declare_global("total");
+        assert_eq!(inner.get("total"), int(0));
+        inner.set("total", int(5));
+        assert_eq!(global.get("total"), int(5));
+        assert_eq!(outer.get("total"), int(10));
+    }
 }
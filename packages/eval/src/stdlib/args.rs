@@ -0,0 +1,35 @@
+use ast::{Call, Expr, Location};
+use errors::{DashlangError, DashlangResult, ErrorKind};
+
+use crate::{ctx::Context, eval, scope::Scope, value::Value};
+
+/// Arguments of a call to a builtin, evaluated as they're taken
+pub struct Args<'a, T: Scope + Clone> {
+    args: std::vec::IntoIter<Expr>,
+    location: Location,
+    ctx: &'a Context<T>,
+}
+impl<'a, T: Scope + Clone> Args<'a, T> {
+    pub fn new(call: Call, ctx: &'a Context<T>) -> Self {
+        Self {
+            args: call.args.into_iter(),
+            location: call.location,
+            ctx,
+        }
+    }
+    pub fn next(&mut self, name: &str) -> DashlangResult<(Value, Location)> {
+        let arg = self.args.next().ok_or_else(|| {
+            DashlangError::new(
+                &format!("Expected '{name}' arg, but none was provided"),
+                ErrorKind::WrongArgs,
+            )
+            .location(self.location)
+        })?;
+        let location = arg.get_location();
+        Ok((eval(arg, self.ctx)?, location))
+    }
+}
+
+pub fn wrong_arg(message: &str, location: Location) -> DashlangError {
+    DashlangError::new(message, ErrorKind::WrongArgs).location(location)
+}
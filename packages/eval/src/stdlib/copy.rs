@@ -0,0 +1,20 @@
+use ast::Call;
+use errors::DashlangResult;
+
+use super::args::Args;
+use crate::{scope::Scope, value::Value, Context};
+
+/// Vectors and maps inside the copy are still shared
+pub fn stdlib_copy<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Value> {
+    let value = Args::new(call, ctx).next("value")?.0;
+    Ok(match value {
+        Value::Vector(vector) => Value::vector(vector.borrow().clone()),
+        Value::Map(map) => Value::map(map.borrow().clone()),
+        value => value,
+    })
+}
+
+pub fn stdlib_deep_copy<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Value> {
+    let value = Args::new(call, ctx).next("value")?.0;
+    Ok(value.deep_copy())
+}
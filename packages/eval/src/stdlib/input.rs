@@ -1,17 +1,14 @@
 use std::io;
 
-use ast::{Call, Literal};
+use ast::Call;
 use errors::{DashlangError, DashlangResult, ErrorKind};
 
-use crate::{scope::Scope, Context};
+use crate::{scope::Scope, value::Value, Context};
 
-pub fn stdlib_input<T: Scope>(_ctx: &Context<T>, call: Call) -> DashlangResult<Literal> {
+pub fn stdlib_input<T: Scope>(_ctx: &Context<T>, call: Call) -> DashlangResult<Value> {
     let mut input = String::new();
     match io::stdin().read_line(&mut input) {
-        Ok(_) => Ok(Literal::String(ast::Str {
-            value: input,
-            location: call.location,
-        })),
+        Ok(_) => Ok(Value::String(input)),
         Err(_) => Err(
             DashlangError::new("Could not get input", ErrorKind::Unknown).location(call.location),
         ),
@@ -1,30 +1,23 @@
-use ast::{Call, Int, Literal};
+use ast::Call;
 use errors::{DashlangError, DashlangResult, ErrorKind};
+use num_bigint::BigInt;
 
-use crate::{eval, scope::Scope, Context};
+use super::args::{wrong_arg, Args};
+use crate::{binary_expr::big_int::big_int_value, scope::Scope, value::Value, Context};
 
-pub fn stdlib_len<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Literal> {
-    let mut iter_args = call.args.into_iter();
-    let item = eval(
-        iter_args.next().ok_or(DashlangError::new(
-            "Expected 'item' arg",
-            ErrorKind::WrongArgs,
-        ))?,
-        ctx,
-    )?;
+pub fn stdlib_len<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Value> {
+    let location = call.location;
+    let (item, item_location) = Args::new(call, ctx).next("item")?;
     match item {
-        Literal::String(val) => Ok(Literal::Int(ast::Int {
-            value: val.value.len() as i64,
-            location: call.location,
-        })),
-        Literal::Vector(val) => Ok(Literal::Int(Int {
-            value: val.value.len() as i64,
-            location: call.location,
-        })),
-        _ => Err(DashlangError::new(
-            "Could not get length: unsuported operation",
-            ErrorKind::Unknown,
-        )
-        .location(call.location)),
+        Value::String(val) => Ok(Value::Int(val.chars().count() as i64)),
+        Value::Vector(val) => Ok(Value::Int(val.borrow().len() as i64)),
+        Value::Range(range) if ctx.big_ints => Ok(big_int_value(BigInt::from(range.len))),
+        Value::Range(range) => i64::try_from(range.len).map(Value::Int).map_err(|_| {
+            DashlangError::new("Integer overflow", ErrorKind::Arithmetic).location(location)
+        }),
+        _ => Err(wrong_arg(
+            "Expected argument to be a string, a vector or a range",
+            item_location,
+        )),
     }
 }
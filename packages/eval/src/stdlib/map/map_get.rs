@@ -1,45 +1,23 @@
-use ast::{Call, Literal, Null};
-use errors::{DashlangError, DashlangResult, ErrorKind};
+use ast::Call;
+use errors::DashlangResult;
 
-use crate::{eval, scope::Scope, Context};
+use crate::{
+    scope::Scope,
+    stdlib::args::{wrong_arg, Args},
+    value::Value,
+    Context,
+};
 
-pub fn stdlib_map_get<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Literal> {
-    let mut iter_args = call.args.into_iter();
-    let map_arg = iter_args.next().ok_or(
-        DashlangError::new(
-            "Expected 'map' arg, but none was provided",
-            ErrorKind::WrongArgs,
-        )
-        .location(call.location),
-    )?;
-    let map_arg_location = map_arg.get_location();
-    let key_arg = iter_args.next().ok_or(
-        DashlangError::new(
-            "Expected 'key' arg, but none was provided",
-            ErrorKind::WrongArgs,
-        )
-        .location(call.location),
-    )?;
-    let key_arg_location = key_arg.get_location();
-
-    if let Literal::Map(map) = eval(map_arg, ctx)? {
-        if let Literal::String(key) = eval(key_arg, ctx)? {
-            match map.value.get(&key.value) {
-                Some(found) => return eval(found.clone(), ctx),
-                None => {
-                    return Ok(Literal::Null(Null {
-                        location: call.location,
-                    }))
-                }
-            }
-        }
-        return Err(
-            DashlangError::new("Expected argument to be a string", ErrorKind::WrongArgs)
-                .location(key_arg_location),
-        );
-    }
-    Err(
-        DashlangError::new("Expected argument to be a map", ErrorKind::WrongArgs)
-            .location(map_arg_location),
-    )
+pub fn stdlib_map_get<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Value> {
+    let mut args = Args::new(call, ctx);
+    let (map, map_location) = args.next("map")?;
+    let (key, key_location) = args.next("key")?;
+    let Value::Map(map) = map else {
+        return Err(wrong_arg("Expected argument to be a map", map_location));
+    };
+    let Value::String(key) = key else {
+        return Err(wrong_arg("Expected key to be a string", key_location));
+    };
+    let found = map.borrow().get(&key).cloned();
+    Ok(found.unwrap_or(Value::Null))
 }
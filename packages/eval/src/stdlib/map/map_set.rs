@@ -1,44 +1,24 @@
-use ast::{Call, Expr, Literal};
-use errors::{DashlangError, DashlangResult, ErrorKind};
+use ast::Call;
+use errors::DashlangResult;
 
-use crate::{eval, scope::Scope, Context};
+use crate::{
+    scope::Scope,
+    stdlib::args::{wrong_arg, Args},
+    value::Value,
+    Context,
+};
 
-pub fn stdlib_map_set<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Literal> {
-    let mut iter_args = call.args.into_iter();
-    let arg_map = iter_args.next().ok_or_else(|| {
-        DashlangError::new(
-            "Expected 'map' arg, but none was provided",
-            ErrorKind::WrongArgs,
-        )
-        .location(call.location)
-    })?;
-    let arg_key = iter_args.next().ok_or_else(|| {
-        DashlangError::new(
-            "Expected 'key' arg, but none was provided",
-            ErrorKind::WrongArgs,
-        )
-        .location(call.location)
-    })?;
-    let arg_value = iter_args.next().ok_or_else(|| {
-        DashlangError::new(
-            "Expected 'value' arg, but none was provided",
-            ErrorKind::WrongArgs,
-        )
-        .location(call.location)
-    })?;
-    let map = eval(arg_map, ctx)?;
-    if let Literal::Map(mut lit_map) = map {
-        if let Expr::Literal(Literal::String(key)) = arg_key {
-            lit_map.value.insert(key.value, arg_value);
-            return Ok(Literal::Map(lit_map));
-        }
-        return Err(
-            DashlangError::new("Expected key to be string", ErrorKind::WrongArgs)
-                .location(call.location),
-        );
-    }
-    Err(
-        DashlangError::new("Expected arg to be a map", ErrorKind::WrongArgs)
-            .location(call.location),
-    )
+pub fn stdlib_map_set<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Value> {
+    let mut args = Args::new(call, ctx);
+    let (map, map_location) = args.next("map")?;
+    let (key, key_location) = args.next("key")?;
+    let (value, _) = args.next("value")?;
+    let Value::Map(map) = map else {
+        return Err(wrong_arg("Expected argument to be a map", map_location));
+    };
+    let Value::String(key) = key else {
+        return Err(wrong_arg("Expected key to be a string", key_location));
+    };
+    map.borrow_mut().insert(key, value);
+    Ok(Value::Map(map))
 }
@@ -1,16 +1,27 @@
+mod args;
+mod copy;
 mod input;
+mod insert;
 mod len;
 mod map;
 mod nth;
+mod pop;
 mod push;
+mod remove;
 pub mod stdio;
+mod to_vector;
 
 use std::rc::Rc;
 
+use copy::{stdlib_copy, stdlib_deep_copy};
 use input::stdlib_input;
+use insert::stdlib_insert;
 use len::stdlib_len;
 use nth::stdlib_nth;
+use pop::stdlib_pop;
 use push::stdlib_push;
+use remove::stdlib_remove;
+use to_vector::stdlib_to_vector;
 
 use crate::{
     scope::Scope,
@@ -18,6 +29,7 @@ use crate::{
     Extension, Plugin,
 };
 
+#[derive(Default)]
 pub struct Stdlib;
 impl Stdlib {
     pub fn new() -> Self {
@@ -45,6 +57,42 @@ impl<T: Scope + Clone> Plugin<T> for Stdlib {
                     implementation: Rc::new(|ctx, call| stdlib_push(ctx, call)),
                 },
             ),
+            (
+                "pop",
+                Extension {
+                    implementation: Rc::new(|ctx, call| stdlib_pop(ctx, call)),
+                },
+            ),
+            (
+                "insert",
+                Extension {
+                    implementation: Rc::new(|ctx, call| stdlib_insert(ctx, call)),
+                },
+            ),
+            (
+                "remove",
+                Extension {
+                    implementation: Rc::new(|ctx, call| stdlib_remove(ctx, call)),
+                },
+            ),
+            (
+                "copy",
+                Extension {
+                    implementation: Rc::new(|ctx, call| stdlib_copy(ctx, call)),
+                },
+            ),
+            (
+                "deep_copy",
+                Extension {
+                    implementation: Rc::new(|ctx, call| stdlib_deep_copy(ctx, call)),
+                },
+            ),
+            (
+                "to_vector",
+                Extension {
+                    implementation: Rc::new(|ctx, call| stdlib_to_vector(ctx, call)),
+                },
+            ),
             (
                 "input",
                 Extension {
@@ -1,23 +1,37 @@
-use ast::{Call, Literal};
+use ast::Call;
 use errors::{DashlangError, DashlangResult, ErrorKind};
 
-use crate::{eval, scope::Scope, Context};
+use super::args::{wrong_arg, Args};
+use crate::{scope::Scope, value::Value, Context};
 
-pub fn stdlib_nth<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Literal> {
-    let mut iter_args = call.args.into_iter();
-    let value = eval(iter_args.next().unwrap(), ctx)?;
-    let index = eval(iter_args.next().unwrap(), ctx)?;
-    if let Literal::Int(int_index) = index {
-        if let Literal::Vector(vec) = value {
-            if (int_index.value as usize) < vec.value.len() {
-                return eval(vec.value[int_index.value as usize].clone(), ctx);
-            }
-            return Err(DashlangError::new("Index out of bound", ErrorKind::Unknown)
-                .location(call.location));
-        }
-        return Err(
-            DashlangError::new("Index out of bound", ErrorKind::Unknown).location(call.location)
-        );
+pub fn stdlib_nth<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Value> {
+    let location = call.location;
+    let mut args = Args::new(call, ctx);
+    let (value, value_location) = args.next("value")?;
+    let (index, index_location) = args.next("index")?;
+    let Value::Vector(vector) = value else {
+        return Err(wrong_arg(
+            "Expected argument to be a vector",
+            value_location,
+        ));
+    };
+    let Value::Int(index) = index else {
+        return Err(wrong_arg(
+            "Expected argument to be an integer",
+            index_location,
+        ));
+    };
+    let vector = vector.borrow();
+    let len = vector.len();
+    match usize::try_from(index)
+        .ok()
+        .and_then(|position| vector.get(position))
+    {
+        Some(found) => Ok(found.clone()),
+        None => Err(DashlangError::new(
+            &format!("Index out of bounds: the length is {len} but the index is {index}"),
+            ErrorKind::OutOfBounds,
+        )
+        .location(location)),
     }
-    Err(DashlangError::new("Index out of bound", ErrorKind::Unknown).location(call.location))
 }
@@ -0,0 +1,18 @@
+use ast::Call;
+use errors::{DashlangError, DashlangResult, ErrorKind};
+
+use super::args::{wrong_arg, Args};
+use crate::{scope::Scope, value::Value, Context};
+
+pub fn stdlib_pop<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Value> {
+    let location = call.location;
+    let mut args = Args::new(call, ctx);
+    let Value::Vector(vector) = args.next("vector")?.0 else {
+        return Err(wrong_arg("Expected argument to be a vector", location));
+    };
+    let popped = vector.borrow_mut().pop();
+    popped.ok_or_else(|| {
+        DashlangError::new("Cannot pop from an empty vector", ErrorKind::OutOfBounds)
+            .location(location)
+    })
+}
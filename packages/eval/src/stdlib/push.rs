@@ -1,41 +1,29 @@
-use ast::{Call, Expr, Literal};
-use errors::{DashlangError, DashlangResult, ErrorKind};
+use ast::Call;
+use errors::DashlangResult;
 
-use crate::{eval, scope::Scope, Context};
+use super::args::{wrong_arg, Args};
+use crate::{scope::Scope, value::Value, Context};
 
-pub fn stdlib_push<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Literal> {
-    let mut iter_args = call.args.into_iter();
-    let base = eval(
-        iter_args.next().ok_or(
-            DashlangError::new("Expected 'base' argument", ErrorKind::WrongArgs)
-                .location(call.location),
-        )?,
-        ctx,
-    )?;
-    let item = eval(
-        iter_args.next().ok_or(DashlangError::new(
-            "Expected 'item' argument",
-            ErrorKind::WrongArgs,
-        ))?,
-        ctx,
-    )?;
+pub fn stdlib_push<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Value> {
+    let mut args = Args::new(call, ctx);
+    let (base, base_location) = args.next("base")?;
+    let (item, item_location) = args.next("item")?;
     match base {
-        Literal::String(mut val) => match item {
-            Literal::String(str_push) => {
-                val.value.push_str(&str_push.value);
-                Ok(Literal::String(val))
+        Value::String(mut val) => match item {
+            Value::String(str_push) => {
+                val.push_str(&str_push);
+                Ok(Value::String(val))
             }
-            _ => Err(
-                DashlangError::new("Unsuported operation", ErrorKind::Unknown)
-                    .location(call.location),
-            ),
+            _ => Err(wrong_arg("Expected item to be a string", item_location)),
         },
-        Literal::Vector(mut vector) => {
-            vector.value.push(Expr::Literal(item));
-            Ok(Literal::Vector(vector))
+        // The vector is changed in place, and returned so `items = push(items, item)` keeps working
+        Value::Vector(vector) => {
+            vector.borrow_mut().push(item);
+            Ok(Value::Vector(vector))
         }
-        _ => Err(
-            DashlangError::new("Unsuported operation", ErrorKind::Unknown).location(call.location),
-        ),
+        _ => Err(wrong_arg(
+            "Expected argument to be a vector or a string",
+            base_location,
+        )),
     }
 }
@@ -0,0 +1,42 @@
+use ast::Call;
+use errors::{DashlangError, DashlangResult, ErrorKind};
+
+use super::args::{wrong_arg, Args};
+use crate::{scope::Scope, value::Value, Context};
+
+pub fn stdlib_remove<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Value> {
+    let location = call.location;
+    let mut args = Args::new(call, ctx);
+    let (collection, collection_location) = args.next("collection")?;
+    let (key, key_location) = args.next("key")?;
+    match (collection, key) {
+        (Value::Vector(vector), Value::Int(index)) => {
+            let mut vector = vector.borrow_mut();
+            let len = vector.len();
+            match usize::try_from(index) {
+                Ok(position) if position < len => Ok(vector.remove(position)),
+                _ => Err(DashlangError::new(
+                    &format!("Index out of bounds: the length is {len} but the index is {index}"),
+                    ErrorKind::OutOfBounds,
+                )
+                .location(location)),
+            }
+        }
+        (Value::Vector(_), _) => Err(wrong_arg("Expected index to be an integer", key_location)),
+        (Value::Map(map), Value::String(key)) => {
+            let removed = map.borrow_mut().remove(&key);
+            removed.ok_or_else(|| {
+                DashlangError::new(
+                    &format!("Key '{key}' not found in map"),
+                    ErrorKind::MissingKey,
+                )
+                .location(location)
+            })
+        }
+        (Value::Map(_), _) => Err(wrong_arg("Expected key to be a string", key_location)),
+        _ => Err(wrong_arg(
+            "Expected argument to be a vector or a map",
+            collection_location,
+        )),
+    }
+}
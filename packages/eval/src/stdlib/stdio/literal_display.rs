@@ -1,58 +1,66 @@
-use ast::Literal;
-use errors::{DashlangError, DashlangResult};
+use std::rc::Rc;
 
-use crate::{ctx::Context, eval, scope::Scope};
+use crate::value::Value;
 
-pub fn stdlib_literal_display<T: Scope + Clone>(
-    value: &Literal,
-    ctx: &Context<T>,
-) -> Result<String, DashlangError> {
+pub fn stdlib_literal_display(value: &Value) -> String {
+    display(value, &mut vec![])
+}
+
+fn display_all(values: &[Value], displaying: &mut Vec<*const ()>) -> String {
+    values
+        .iter()
+        .map(|value| display(value, displaying))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// `displaying` holds the vectors and maps being displayed further up. One that contains itself
+/// is shown as `[...]` or `{...}` the second time, instead of forever
+fn display(value: &Value, displaying: &mut Vec<*const ()>) -> String {
     match value {
-        Literal::Closure(_) => Ok("Closure".to_string()),
-        Literal::Int(val) => Ok(format!("{}", val.value)),
-        Literal::Float(val) => Ok(format!("{}", val.value)),
-        Literal::String(val) => Ok(val.clone().value),
-        Literal::Bool(val) => Ok(if val.value {
-            "True".to_string()
-        } else {
-            "False".to_string()
-        }),
-        Literal::Vector(val) => {
-            let display_args: Result<Vec<String>, DashlangError> = val
-                .clone()
-                .value
-                .into_iter()
-                .map(|item| stdlib_literal_display(&eval(item.clone(), ctx)?, ctx))
-                .collect();
-            match display_args {
-                Ok(args) => Ok(format!("[{}]", args.join(", "))),
-                Err(err) => Err(err),
+        Value::Closure(_) => "Closure".to_string(),
+        Value::Int(val) => format!("{val}"),
+        Value::BigInt(val) => format!("{val}"),
+        Value::Float(val) => format!("{val}"),
+        Value::String(val) => val.clone(),
+        Value::Bool(val) => if *val { "True" } else { "False" }.to_string(),
+        Value::Vector(val) => {
+            let pointer = Rc::as_ptr(val).cast();
+            if displaying.contains(&pointer) {
+                return "[...]".to_string();
             }
+            displaying.push(pointer);
+            let displayed = format!("[{}]", display_all(&val.borrow(), displaying));
+            displaying.pop();
+            displayed
         }
-        Literal::Null(_) => Ok("Null".to_string()),
-        Literal::Void(_) => Ok("Void".to_string()),
-        Literal::Tuple(tup) => {
-            let display_values: DashlangResult<Vec<String>> = tup
-                .clone()
-                .value
-                .into_iter()
-                .map(|item| stdlib_literal_display(&eval(item.clone(), ctx)?, ctx))
-                .collect();
-            match display_values {
-                Ok(args) => Ok(format!("({})", args.join(", "))),
-                Err(err) => Err(err),
+        Value::Null => "Null".to_string(),
+        Value::Void => "Void".to_string(),
+        Value::Tuple(tup) => format!("({})", display_all(tup, displaying)),
+        Value::Map(map) => {
+            let pointer = Rc::as_ptr(map).cast();
+            if displaying.contains(&pointer) {
+                return "{...}".to_string();
             }
+            displaying.push(pointer);
+            let formated_attributes: Vec<String> = map
+                .borrow()
+                .iter()
+                .map(|(symbol, value)| format!("{symbol}: {}", display(value, displaying)))
+                .collect();
+            displaying.pop();
+            format!("{{ {} }}", formated_attributes.join(", "))
         }
-        Literal::Map(map) => {
-            let mut formated_attributes: Vec<String> = vec![];
-            for (symbol, value) in map.value.iter() {
-                formated_attributes.push(format!(
-                    "{symbol}: {}",
-                    stdlib_literal_display(&eval(value.clone(), ctx)?, ctx)?
-                ));
+        Value::Atom(atom) => format!(":{atom}"),
+        Value::Range(range) => {
+            let bounds = match (range.end(), range.last()) {
+                (None, Some(last)) => format!("{}..={last}", range.start),
+                (end, _) => format!("{}..{}", range.start, end.unwrap_or(range.start)),
+            };
+            match range.step {
+                1 => bounds,
+                step => format!("{bounds} step {step}"),
             }
-            Ok(format!("{{ {} }}", formated_attributes.join(", ")))
         }
-        Literal::Atom(atom) => Ok(format!(":{}", atom.value)),
     }
 }
@@ -4,13 +4,14 @@ use crate::{
 };
 use std::rc::Rc;
 
-mod literal_display;
+pub mod literal_display;
 pub mod print;
 pub mod println;
 
 use print::stdlib_print;
 use println::stdlib_println;
 
+#[derive(Default)]
 pub struct Stdio {}
 impl Stdio {
     pub fn new() -> Self {
@@ -1,20 +1,23 @@
-use ast::{Call, Literal};
+use std::io::{self, Write};
+
+use ast::Call;
 use errors::{DashlangError, ErrorKind};
 
-use crate::{eval, scope::Scope, stdlib::stdio::literal_display::stdlib_literal_display, Context};
+use crate::{
+    scope::Scope,
+    stdlib::{args::Args, stdio::literal_display::stdlib_literal_display},
+    value::Value,
+    Context,
+};
 
 pub fn stdlib_println<T: Scope + Clone>(
     call: Call,
     ctx: &Context<T>,
-) -> Result<Literal, DashlangError> {
-    let mut iter_args = call.args.into_iter();
-    let value = eval(
-        iter_args.next().ok_or(
-            DashlangError::new("Expected 'expr' argument", ErrorKind::WrongArgs)
-                .location(call.location),
-        )?,
-        ctx,
-    )?;
-    println!("{}", stdlib_literal_display(&value, ctx)?);
+) -> Result<Value, DashlangError> {
+    let location = call.location;
+    let value = Args::new(call, ctx).next("expr")?.0;
+    writeln!(io::stdout(), "{}", stdlib_literal_display(&value)).map_err(|_| {
+        DashlangError::new("Could not write to stdout", ErrorKind::Unknown).location(location)
+    })?;
     Ok(value)
 }
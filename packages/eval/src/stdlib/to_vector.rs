@@ -0,0 +1,22 @@
+use ast::Call;
+use errors::{DashlangError, DashlangResult, ErrorKind};
+
+use super::args::Args;
+use crate::{iteration::iterate, scope::Scope, value::Value, Context};
+
+pub fn stdlib_to_vector<T: Scope + Clone>(ctx: &Context<T>, call: Call) -> DashlangResult<Value> {
+    let (value, location) = Args::new(call, ctx).next("value")?;
+    let elements = iterate(value, location)?;
+    // Ranges can be far bigger than memory, which has to fail without aborting
+    let len = elements.size_hint().0;
+    let mut vector = vec![];
+    vector.try_reserve_exact(len).map_err(|_| {
+        DashlangError::new(
+            &format!("Cannot make a vector of {len} elements: not enough memory"),
+            ErrorKind::InvalidOperation,
+        )
+        .location(location)
+    })?;
+    vector.extend(elements);
+    Ok(Value::vector(vector))
+}
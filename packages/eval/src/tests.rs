@@ -1,11 +1,221 @@
 use ast::{
-    AssignmentExpr, BinaryExpr, BinaryOperator, Closure, Float, If, Location, Null, Return, Str,
-    Symbol, While,
+    AssignmentExpr, BinaryExpr, BinaryOperator, Boolean, Break, Closure, Continue,
+    DestructuringAsignment, Float, For, If, Int, Location, Map, MapPattern, Match, MatchArm,
+    Nonlocal, Null, Pattern, RestPattern, Return, Str, Symbol, Tuple, TuplePattern, Vector,
+    VectorPattern, While,
 };
 use scope::HashScope;
 
 use super::*;
 
+// Builders for the expressions, statements and values used across the tests
+fn int_expr(value: i64) -> Expr {
+    Expr::Literal(Literal::Int(Int {
+        value,
+        location: Default::default(),
+    }))
+}
+fn symbol_expr(value: &str) -> Expr {
+    Expr::Symbol(Symbol {
+        value: value.to_owned(),
+        location: Location::default(),
+    })
+}
+fn null_expr() -> Expr {
+    Expr::Literal(Literal::Null(Null {
+        location: Default::default(),
+    }))
+}
+fn bool_expr(value: bool) -> Expr {
+    Expr::Literal(Literal::Bool(Boolean {
+        value,
+        location: Default::default(),
+    }))
+}
+fn str_expr(value: &str) -> Expr {
+    Expr::Literal(Literal::String(Str {
+        value: value.to_owned(),
+        location: Default::default(),
+    }))
+}
+fn access_expr(target: Expr, key: Expr, location: Location) -> Expr {
+    Expr::Access(Box::new(ast::Access {
+        target,
+        key,
+        location,
+    }))
+}
+fn path_assign_expr(symbol: &str, path: Vec<Expr>, value: Expr, location: Location) -> Expr {
+    Expr::Assignment(AssignmentExpr {
+        symbol: symbol.to_owned(),
+        path,
+        operator: None,
+        value: Box::new(value),
+        location,
+    })
+}
+fn closure_expr(params: &[&str], body: Program) -> Expr {
+    Expr::Literal(Literal::Closure(Closure {
+        params: params.iter().map(|param| binding(param)).collect(),
+        body,
+        location: Location::default(),
+    }))
+}
+fn call_expr(callee: Expr, args: Vec<Expr>) -> Expr {
+    Expr::Call(Call {
+        callee: Box::new(callee),
+        args,
+        location: Location::default(),
+    })
+}
+fn add_expr(left: Expr, right: Expr) -> Expr {
+    Expr::BinaryExpr(Box::new(BinaryExpr::new(left, right, BinaryOperator::Add)))
+}
+fn float_expr(value: f64) -> Expr {
+    Expr::Literal(Literal::Float(Float {
+        value,
+        location: Location::default(),
+    }))
+}
+fn binary_expr(left: Expr, right: Expr, operator: BinaryOperator) -> Expr {
+    Expr::BinaryExpr(Box::new(BinaryExpr::new(left, right, operator)))
+}
+fn vector_expr(items: Vec<Expr>) -> Expr {
+    Expr::Literal(Literal::Vector(Vector {
+        value: items,
+        location: Location::default(),
+    }))
+}
+fn tuple_expr(items: Vec<Expr>) -> Expr {
+    Expr::Literal(Literal::Tuple(Tuple {
+        value: items,
+        location: Location::default(),
+    }))
+}
+fn map_expr(attributes: Vec<(&str, Expr)>) -> Expr {
+    Expr::Literal(Literal::Map(Map {
+        value: attributes
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value))
+            .collect(),
+        location: Location::default(),
+    }))
+}
+fn range_expr(start: Expr, end: Expr, step: Option<Expr>, inclusive: bool) -> Expr {
+    Expr::Range(Box::new(ast::Range {
+        start,
+        end,
+        step,
+        inclusive,
+        location: Location::default(),
+    }))
+}
+fn match_expr(value: Expr, arms: Vec<(Pattern, Option<Expr>, Expr)>) -> Expr {
+    Expr::Match(Box::new(Match {
+        value,
+        arms: arms
+            .into_iter()
+            .map(|(pattern, guard, value)| MatchArm {
+                pattern,
+                guard,
+                value,
+                location: Location::default(),
+            })
+            .collect(),
+        location: Location::default(),
+    }))
+}
+fn destructuring_expr(pattern: Pattern, value: Expr) -> Expr {
+    Expr::DestructuringAsignment(DestructuringAsignment {
+        location: Location::default(),
+        pattern,
+        value: Box::new(value),
+    })
+}
+fn if_expr(cond: Expr, body: Program, else_block: Option<Program>) -> Expr {
+    Expr::If(Box::new(If {
+        cond,
+        body,
+        else_block,
+        location: Location::default(),
+    }))
+}
+fn block_expr(body: Program) -> Expr {
+    Expr::Block(ast::Block {
+        body,
+        location: Location::default(),
+    })
+}
+fn add_assign_stmt(symbol: &str, value: Expr) -> Stmt {
+    Stmt::Expr(Expr::Assignment(AssignmentExpr {
+        symbol: symbol.to_owned(),
+        path: vec![],
+        operator: None,
+        value: Box::new(Expr::BinaryExpr(Box::new(BinaryExpr::new(
+            symbol_expr(symbol),
+            value,
+            BinaryOperator::Add,
+        )))),
+        location: Location::default(),
+    }))
+}
+fn if_stmt(cond: Expr, body: Program) -> Stmt {
+    Stmt::If(If {
+        cond,
+        body,
+        else_block: None,
+        location: Location::default(),
+    })
+}
+fn return_stmt(value: Expr) -> Stmt {
+    Stmt::Return(Return {
+        value,
+        location: Location::default(),
+    })
+}
+fn assign_stmt(symbol: &str, value: Expr) -> Stmt {
+    Stmt::Expr(path_assign_expr(symbol, vec![], value, Location::default()))
+}
+fn for_in_stmt(symbols: &[&str], iterable: Expr, body: Program) -> Stmt {
+    Stmt::ForIn(Box::new(ast::ForIn {
+        label: None,
+        pattern: match symbols {
+            [symbol] => binding(symbol),
+            _ => tuple_pattern(symbols.iter().map(|symbol| binding(symbol)).collect()),
+        },
+        iterable,
+        body,
+        location: Location::default(),
+    }))
+}
+fn atom_literal(value: &str) -> Literal {
+    Literal::Atom(ast::Atom {
+        value: value.to_owned(),
+        location: Location::default(),
+    })
+}
+fn binding(symbol: &str) -> Pattern {
+    Pattern::Binding(Symbol {
+        value: symbol.to_owned(),
+        location: Location::default(),
+    })
+}
+fn tuple_pattern(items: Vec<Pattern>) -> Pattern {
+    Pattern::Tuple(TuplePattern {
+        items,
+        location: Location::default(),
+    })
+}
+fn int_value(value: i64) -> Value {
+    Value::Int(value)
+}
+fn float_value(value: f64) -> Value {
+    Value::Float(value)
+}
+fn big_int(value: &str) -> Value {
+    Value::BigInt(value.parse().unwrap())
+}
+
 #[test]
 fn eval_primitive() {
     let scope = HashScope::default();
@@ -17,13 +227,7 @@ fn eval_primitive() {
         })),
         &ctx,
     );
-    assert_eq!(
-        result,
-        Ok(Literal::Int(Int {
-            value: 1,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::Int(1)));
 
     let result = eval(
         Expr::Literal(Literal::Bool(Boolean {
@@ -32,13 +236,7 @@ fn eval_primitive() {
         })),
         &ctx,
     );
-    assert_eq!(
-        result,
-        Ok(Literal::Bool(Boolean {
-            value: true,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::Bool(true)));
 
     let result = eval(
         Expr::Literal(Literal::String(Str {
@@ -47,13 +245,7 @@ fn eval_primitive() {
         })),
         &ctx,
     );
-    assert_eq!(
-        result,
-        Ok(Literal::String(Str {
-            value: String::from("test"),
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::String(String::from("test"))));
 
     let result = eval(
         Expr::Literal(Literal::Float(Float {
@@ -62,17 +254,13 @@ fn eval_primitive() {
         })),
         &ctx,
     );
-    assert_eq!(
-        result,
-        Ok(Literal::Float(Float {
-            value: 1.5,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::Float(1.5)));
 
     eval(
         Expr::Assignment(AssignmentExpr {
             symbol: String::from("name"),
+            path: vec![],
+            operator: None,
             value: Box::new(Expr::Literal(Literal::Int(Int {
                 value: 4,
                 location: Default::default(),
@@ -87,13 +275,7 @@ fn eval_primitive() {
         location: Location::default(),
     });
     let found_value = eval(symbol, &ctx);
-    assert_eq!(
-        found_value,
-        Ok(Literal::Int(Int {
-            value: 4,
-            location: Default::default()
-        }))
-    )
+    assert_eq!(found_value, Ok(Value::Int(4)))
 }
 #[test]
 fn eval_add_operation() {
@@ -126,13 +308,7 @@ fn eval_add_operation() {
         location: Location::default(),
     }));
     let result = eval(op, &ctx);
-    assert_eq!(
-        result,
-        Ok(Literal::Float(Float {
-            value: 19.5,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::Float(19.5)));
 }
 #[test]
 #[should_panic]
@@ -184,13 +360,7 @@ fn eval_sub_operation() {
         location: Location::default(),
     }));
     let result = eval(op, &ctx);
-    assert_eq!(
-        result,
-        Ok(Literal::Float(Float {
-            value: 3.0,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::Float(3.0)));
 }
 #[test]
 fn eval_multiplication() {
@@ -224,26 +394,14 @@ fn eval_multiplication() {
         location: Location::default(),
     }));
     let result = eval(op, &ctx);
-    assert_eq!(
-        result,
-        Ok(Literal::Float(Float {
-            value: 38.5,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::Float(38.5)));
 }
 #[test]
 fn eval_division() {
     let scope = HashScope::default();
     let ctx = Context::new(scope);
 
-    ctx.scope.set(
-        "age",
-        Literal::Int(Int {
-            value: 10,
-            location: Default::default(),
-        }),
-    );
+    ctx.scope.set("age", Value::Int(10));
 
     let op = Expr::BinaryExpr(Box::new(BinaryExpr {
         left: Expr::BinaryExpr(Box::new(BinaryExpr::new(
@@ -272,13 +430,7 @@ fn eval_division() {
         location: Location::default(),
     }));
     let result = eval(op, &ctx);
-    assert_eq!(
-        result,
-        Ok(Literal::Float(Float {
-            value: 15.0,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::Float(15.0)));
 }
 #[test]
 fn eval_gt() {
@@ -297,13 +449,7 @@ fn eval_gt() {
         BinaryOperator::Gt,
     )));
     let result = eval(op, &ctx);
-    assert_eq!(
-        result,
-        Ok(Literal::Bool(Boolean {
-            value: true,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::Bool(true)));
 }
 #[test]
 fn truthy_or_falsy() {
@@ -449,13 +595,7 @@ fn logical_operations() {
         })),
         BinaryOperator::Or,
     )));
-    assert_eq!(
-        eval(op, &ctx),
-        Ok(Literal::Bool(Boolean {
-            value: true,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(eval(op, &ctx), Ok(Value::Bool(true)));
 
     let op = Expr::BinaryExpr(Box::new(BinaryExpr::new(
         Expr::Literal(Literal::Bool(Boolean {
@@ -468,13 +608,7 @@ fn logical_operations() {
         })),
         BinaryOperator::And,
     )));
-    assert_eq!(
-        eval(op, &ctx),
-        Ok(Literal::Bool(Boolean {
-            value: false,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(eval(op, &ctx), Ok(Value::Bool(false)));
 
     let op = Expr::BinaryExpr(Box::new(BinaryExpr::new(
         Expr::Literal(Literal::Bool(Boolean {
@@ -487,13 +621,7 @@ fn logical_operations() {
         })),
         BinaryOperator::And,
     )));
-    assert_eq!(
-        eval(op, &ctx),
-        Ok(Literal::Bool(Boolean {
-            value: true,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(eval(op, &ctx), Ok(Value::Bool(true)));
 
     let op = Expr::BinaryExpr(Box::new(BinaryExpr::new(
         Expr::Literal(Literal::Bool(Boolean {
@@ -506,22 +634,15 @@ fn logical_operations() {
         })),
         BinaryOperator::Or,
     )));
-    assert_eq!(
-        eval(op, &ctx),
-        Ok(Literal::Bool(Boolean {
-            value: false,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(eval(op, &ctx), Ok(Value::Bool(false)));
 }
 #[test]
 fn test_eval_call() {
     let scope = HashScope::default();
     let ctx = Context::new(scope);
-    ctx.scope.set(
-        "greet",
-        Literal::Closure(ast::Closure {
-            params: vec![String::from("name")],
+    let greet = eval(
+        Expr::Literal(Literal::Closure(ast::Closure {
+            params: vec![binding("name")],
             body: vec![Stmt::Return(Return {
                 value: Expr::Symbol(Symbol {
                     value: String::from("name"),
@@ -530,10 +651,16 @@ fn test_eval_call() {
                 location: Location::default(),
             })],
             location: Location::default(),
-        }),
-    );
+        })),
+        &ctx,
+    )
+    .unwrap();
+    ctx.scope.set("greet", greet);
     let call = Expr::Call(Call {
-        symbol: String::from("greet"),
+        callee: Box::new(Expr::Symbol(Symbol {
+            value: String::from("greet"),
+            location: Default::default(),
+        })),
         args: vec![Expr::Literal(Literal::String(Str {
             value: String::from("John"),
             location: Default::default(),
@@ -541,20 +668,14 @@ fn test_eval_call() {
         location: Location::default(),
     });
     let result = eval(call, &ctx);
-    assert_eq!(
-        result,
-        Ok(Literal::String(Str {
-            value: String::from("John"),
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::String(String::from("John"))));
 }
 #[test]
 fn test_if_else() {
     let scope = HashScope::default();
     let ctx = Context::new(scope);
     let is_adult_fn = Closure {
-        params: vec![String::from("age")],
+        params: vec![binding("age")],
         body: vec![Stmt::If(If {
             cond: Expr::BinaryExpr(Box::new(BinaryExpr::new(
                 Expr::Symbol(Symbol {
@@ -593,9 +714,13 @@ fn test_if_else() {
     //      false
     //  }
     // }
-    ctx.scope.set("is_adult", Literal::Closure(is_adult_fn));
+    let is_adult_fn = eval(Expr::Literal(Literal::Closure(is_adult_fn)), &ctx).unwrap();
+    ctx.scope.set("is_adult", is_adult_fn);
     let call = Expr::Call(Call {
-        symbol: String::from("is_adult"),
+        callee: Box::new(Expr::Symbol(Symbol {
+            value: String::from("is_adult"),
+            location: Default::default(),
+        })),
         args: vec![Expr::Literal(Literal::Int(Int {
             value: 18,
             location: Default::default(),
@@ -603,16 +728,13 @@ fn test_if_else() {
         location: Location::default(),
     });
     let result = eval(call, &ctx);
-    assert_eq!(
-        result,
-        Ok(Literal::Bool(Boolean {
-            value: true,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::Bool(true)));
 
     let call = Expr::Call(Call {
-        symbol: String::from("is_adult"),
+        callee: Box::new(Expr::Symbol(Symbol {
+            value: String::from("is_adult"),
+            location: Default::default(),
+        })),
         args: vec![Expr::Literal(Literal::Int(Int {
             value: 17,
             location: Default::default(),
@@ -620,26 +742,15 @@ fn test_if_else() {
         location: Location::default(),
     });
     let result = eval(call, &ctx);
-    assert_eq!(
-        result,
-        Ok(Literal::Bool(Boolean {
-            value: false,
-            location: Default::default()
-        }))
-    );
+    assert_eq!(result, Ok(Value::Bool(false)));
 }
 #[test]
 fn test_while_loop() {
     let scope = HashScope::default();
     let ctx = Context::new(scope);
-    ctx.scope.set(
-        "count",
-        Literal::Int(Int {
-            value: 0,
-            location: Default::default(),
-        }),
-    );
+    ctx.scope.set("count", Value::Int(0));
     let program: Program = vec![Stmt::While(While {
+        label: None,
         cond: Expr::BinaryExpr(Box::new(BinaryExpr::new(
             Expr::Symbol(Symbol {
                 value: String::from("count"),
@@ -653,6 +764,8 @@ fn test_while_loop() {
         ))),
         body: vec![Stmt::Expr(Expr::Assignment(AssignmentExpr {
             symbol: String::from("count"),
+            path: vec![],
+            operator: None,
             value: Box::new(Expr::BinaryExpr(Box::new(BinaryExpr::new(
                 Expr::Symbol(Symbol {
                     value: String::from("count"),
@@ -674,13 +787,7 @@ fn test_while_loop() {
     // }
     eval_program(program, &ctx).unwrap();
     let final_count = ctx.scope.get("count");
-    assert_eq!(
-        final_count,
-        Literal::Int(Int {
-            value: 10,
-            location: Default::default()
-        })
-    );
+    assert_eq!(final_count, Value::Int(10));
 }
 #[test]
 fn test_unary_op() {
@@ -698,10 +805,7 @@ fn test_unary_op() {
             })),
             &ctx,
         ),
-        Ok(Literal::Bool(Boolean {
-            value: false,
-            location: Default::default()
-        }))
+        Ok(Value::Bool(false))
     );
     assert_eq!(
         eval(
@@ -715,9 +819,2163 @@ fn test_unary_op() {
             })),
             &ctx,
         ),
-        Ok(Literal::Bool(Boolean {
+        Ok(Value::Bool(true))
+    );
+}
+#[test]
+fn test_string_interpolation() {
+    let scope = HashScope::default();
+    let ctx = Context::new(scope);
+    ctx.scope.set("name", Value::String(String::from("John")));
+    let interpolation = Expr::StringInterpolation(StringInterpolation {
+        parts: vec![
+            Expr::Literal(Literal::String(Str {
+                value: String::from("Hello "),
+                location: Default::default(),
+            })),
+            Expr::Symbol(Symbol {
+                value: String::from("name"),
+                location: Location::default(),
+            }),
+            Expr::Literal(Literal::String(Str {
+                value: String::from(", you are "),
+                location: Default::default(),
+            })),
+            Expr::BinaryExpr(Box::new(BinaryExpr::new(
+                Expr::Literal(Literal::Int(Int {
+                    value: 24,
+                    location: Default::default(),
+                })),
+                Expr::Literal(Literal::Int(Int {
+                    value: 1,
+                    location: Default::default(),
+                })),
+                BinaryOperator::Add,
+            ))),
+            Expr::Literal(Literal::String(Str {
+                value: String::from(" "),
+                location: Default::default(),
+            })),
+            Expr::Literal(Literal::Bool(Boolean {
+                value: true,
+                location: Default::default(),
+            })),
+        ],
+        location: Location::default(),
+    });
+    assert_eq!(
+        eval(interpolation, &ctx),
+        Ok(Value::String(String::from("Hello John, you are 25 True")))
+    );
+}
+#[test]
+fn test_break_while() {
+    let ctx = Context::new(HashScope::default());
+    ctx.scope.set("count", Value::Int(0));
+    // while true { count += 1 if count == 3 { break } }
+    let program: Program = vec![Stmt::While(While {
+        label: None,
+        cond: Expr::Literal(Literal::Bool(Boolean {
+            value: true,
+            location: Default::default(),
+        })),
+        body: vec![
+            add_assign_stmt("count", int_expr(1)),
+            if_stmt(
+                Expr::BinaryExpr(Box::new(BinaryExpr::new(
+                    symbol_expr("count"),
+                    int_expr(3),
+                    BinaryOperator::Eq,
+                ))),
+                vec![Stmt::Break(Break {
+                    label: None,
+                    location: Location::default(),
+                })],
+            ),
+        ],
+        location: Location::default(),
+    })];
+    eval_program(program, &ctx).unwrap();
+    assert_eq!(ctx.scope.get("count"), Value::Int(3));
+}
+#[test]
+fn test_continue_for_runs_iteration() {
+    let ctx = Context::new(HashScope::default());
+    ctx.scope.set("sum", Value::Int(0));
+    // for i = 0; i < 5; i += 1 { if i == 2 { continue } sum += i }
+    let program: Program = vec![Stmt::For(Box::new(For {
+        label: None,
+        init: Stmt::Expr(Expr::Assignment(AssignmentExpr {
+            symbol: String::from("i"),
+            path: vec![],
+            operator: None,
+            value: Box::new(int_expr(0)),
+            location: Location::default(),
+        })),
+        cond: Expr::BinaryExpr(Box::new(BinaryExpr::new(
+            symbol_expr("i"),
+            int_expr(5),
+            BinaryOperator::Lt,
+        ))),
+        iteration: add_assign_stmt("i", int_expr(1)),
+        body: vec![
+            if_stmt(
+                Expr::BinaryExpr(Box::new(BinaryExpr::new(
+                    symbol_expr("i"),
+                    int_expr(2),
+                    BinaryOperator::Eq,
+                ))),
+                vec![Stmt::Continue(Continue {
+                    label: None,
+                    location: Location::default(),
+                })],
+            ),
+            add_assign_stmt("sum", symbol_expr("i")),
+        ],
+        location: Location::default(),
+    }))];
+    eval_program(program, &ctx).unwrap();
+    assert_eq!(ctx.scope.get("sum"), Value::Int(8));
+}
+#[test]
+fn test_labeled_break() {
+    let ctx = Context::new(HashScope::default());
+    ctx.scope.set("count", Value::Int(0));
+    // 'outer: while true { while true { count += 1 break 'outer } count += 10 }
+    let program: Program = vec![Stmt::While(While {
+        label: Some(String::from("outer")),
+        cond: Expr::Literal(Literal::Bool(Boolean {
             value: true,
-            location: Default::default()
-        }))
+            location: Default::default(),
+        })),
+        body: vec![
+            Stmt::While(While {
+                label: None,
+                cond: Expr::Literal(Literal::Bool(Boolean {
+                    value: true,
+                    location: Default::default(),
+                })),
+                body: vec![
+                    add_assign_stmt("count", int_expr(1)),
+                    Stmt::Break(Break {
+                        label: Some(String::from("outer")),
+                        location: Location::default(),
+                    }),
+                ],
+                location: Location::default(),
+            }),
+            add_assign_stmt("count", int_expr(10)),
+        ],
+        location: Location::default(),
+    })];
+    eval_program(program, &ctx).unwrap();
+    assert_eq!(ctx.scope.get("count"), Value::Int(1));
+}
+#[test]
+fn test_else_without_return_falls_through() {
+    let ctx = Context::new(HashScope::default());
+    // if false {} else { x = 1 } return 5
+    let program: Program = vec![
+        Stmt::If(If {
+            cond: bool_expr(false),
+            body: vec![],
+            else_block: Some(vec![Stmt::Expr(Expr::Assignment(AssignmentExpr {
+                symbol: String::from("x"),
+                path: vec![],
+                operator: None,
+                value: Box::new(int_expr(1)),
+                location: Location::default(),
+            }))]),
+            location: Location::default(),
+        }),
+        return_stmt(int_expr(5)),
+    ];
+    assert_eq!(eval_program(program, &ctx), Ok(Value::Int(5)));
+}
+#[test]
+fn test_return_null_from_else() {
+    let ctx = Context::new(HashScope::default());
+    // if false {} else { return null } return 5
+    let program: Program = vec![
+        Stmt::If(If {
+            cond: bool_expr(false),
+            body: vec![],
+            else_block: Some(vec![return_stmt(null_expr())]),
+            location: Location::default(),
+        }),
+        return_stmt(int_expr(5)),
+    ];
+    assert_eq!(eval_program(program, &ctx), Ok(Value::Null));
+}
+#[test]
+fn test_return_void_value_from_if() {
+    let ctx = Context::new(HashScope::default());
+    // if true { return missing } return 1
+    let program: Program = vec![
+        if_stmt(bool_expr(true), vec![return_stmt(symbol_expr("missing"))]),
+        return_stmt(int_expr(1)),
+    ];
+    assert_eq!(eval_program(program, &ctx), Ok(Value::Void));
+}
+#[test]
+fn test_return_null_from_while() {
+    let ctx = Context::new(HashScope::default());
+    // while true { return null } return 1
+    let program: Program = vec![
+        Stmt::While(While {
+            label: None,
+            cond: bool_expr(true),
+            body: vec![return_stmt(null_expr())],
+            location: Location::default(),
+        }),
+        return_stmt(int_expr(1)),
+    ];
+    assert_eq!(eval_program(program, &ctx), Ok(Value::Null));
+}
+#[test]
+fn test_return_from_for() {
+    let ctx = Context::new(HashScope::default());
+    // for i = 0; i < 10; i += 1 { if i == 4 { return i } } return 0
+    let program: Program = vec![
+        Stmt::For(Box::new(For {
+            label: None,
+            init: Stmt::Expr(Expr::Assignment(AssignmentExpr {
+                symbol: String::from("i"),
+                path: vec![],
+                operator: None,
+                value: Box::new(int_expr(0)),
+                location: Location::default(),
+            })),
+            cond: Expr::BinaryExpr(Box::new(BinaryExpr::new(
+                symbol_expr("i"),
+                int_expr(10),
+                BinaryOperator::Lt,
+            ))),
+            iteration: add_assign_stmt("i", int_expr(1)),
+            body: vec![if_stmt(
+                Expr::BinaryExpr(Box::new(BinaryExpr::new(
+                    symbol_expr("i"),
+                    int_expr(4),
+                    BinaryOperator::Eq,
+                ))),
+                vec![return_stmt(symbol_expr("i"))],
+            )],
+            location: Location::default(),
+        })),
+        return_stmt(int_expr(0)),
+    ];
+    assert_eq!(eval_program(program, &ctx), Ok(Value::Int(4)));
+}
+#[test]
+fn test_closure_without_return() {
+    let ctx = Context::new(HashScope::default());
+    let noop = Expr::Literal(Literal::Closure(Closure {
+        params: vec![],
+        body: vec![if_stmt(bool_expr(false), vec![return_stmt(int_expr(1))])],
+        location: Location::default(),
+    }));
+    ctx.scope.set("noop", eval(noop, &ctx).unwrap());
+    let call = Expr::Call(Call {
+        callee: Box::new(Expr::Symbol(Symbol {
+            value: String::from("noop"),
+            location: Default::default(),
+        })),
+        args: vec![],
+        location: Location::default(),
+    });
+    assert_eq!(eval(call, &ctx), Ok(Value::Void));
+}
+#[test]
+fn test_break_outside_loop() {
+    let ctx = Context::new(HashScope::default());
+    let program: Program = vec![Stmt::Break(Break {
+        label: None,
+        location: Location::new(4, 9),
+    })];
+    assert_eq!(
+        eval_program(program, &ctx),
+        Err(
+            DashlangError::new("'break' used outside of a loop", ErrorKind::Unknown)
+                .location(Location::new(4, 9))
+        )
+    );
+}
+#[test]
+fn test_access() {
+    let ctx = Context::new(HashScope::default());
+    let items = Expr::Literal(Literal::Vector(ast::Vector {
+        value: vec![int_expr(1), int_expr(2)],
+        location: Default::default(),
+    }));
+    assert_eq!(
+        eval(
+            access_expr(items.clone(), int_expr(1), Location::default()),
+            &ctx
+        ),
+        Ok(Value::Int(2))
+    );
+    assert_eq!(
+        eval(access_expr(items, int_expr(2), Location::new(0, 8)), &ctx),
+        Err(DashlangError::new(
+            "Index out of bounds: the length is 2 but the index is 2",
+            ErrorKind::OutOfBounds
+        )
+        .location(Location::new(0, 8)))
+    );
+    assert_eq!(
+        eval(
+            access_expr(str_expr("héllo"), int_expr(1), Location::default()),
+            &ctx
+        ),
+        Ok(Value::String(String::from("é")))
+    );
+    let person = Expr::Literal(Literal::Map(ast::Map {
+        value: std::collections::HashMap::from([(String::from("name"), str_expr("John"))]),
+        location: Default::default(),
+    }));
+    assert_eq!(
+        eval(
+            access_expr(person.clone(), str_expr("name"), Location::default()),
+            &ctx
+        ),
+        Ok(Value::String(String::from("John")))
+    );
+    assert_eq!(
+        eval(
+            access_expr(person, str_expr("age"), Location::new(3, 9)),
+            &ctx
+        ),
+        Err(
+            DashlangError::new("Key 'age' not found in map", ErrorKind::MissingKey)
+                .location(Location::new(3, 9))
+        )
+    );
+}
+#[test]
+fn test_path_assignment() {
+    let ctx = Context::new(HashScope::default());
+    ctx.scope
+        .set("items", Value::vector(vec![Value::Int(1), Value::Int(2)]));
+    eval(
+        path_assign_expr("items", vec![int_expr(1)], int_expr(5), Location::default()),
+        &ctx,
+    )
+    .unwrap();
+    assert_eq!(
+        ctx.scope.get("items"),
+        Value::vector(vec![Value::Int(1), Value::Int(5)])
+    );
+    assert_eq!(
+        eval(
+            path_assign_expr(
+                "items",
+                vec![Expr::Literal(Literal::Int(Int {
+                    value: 2,
+                    location: Location::new(6, 7),
+                }))],
+                int_expr(5),
+                Location::new(0, 12)
+            ),
+            &ctx,
+        ),
+        Err(DashlangError::new(
+            "Index out of bounds: the length is 2 but the index is 2",
+            ErrorKind::OutOfBounds
+        )
+        .location(Location::new(0, 7)))
+    );
+}
+#[test]
+fn test_compound_path_assignment_evaluates_keys_once() {
+    let ctx = Context::new(HashScope::default());
+    ctx.scope.set("calls", Value::Int(0));
+    ctx.scope.set("i", Value::Int(0));
+    ctx.scope.set(
+        "items",
+        Value::vector(vec![Value::Int(10), Value::Int(20), Value::Int(30)]),
+    );
+    // next = () { global calls  calls += 1  return 1 }
+    let next = closure_expr(
+        &[],
+        vec![
+            Stmt::Global(ast::Global {
+                symbols: vec![String::from("calls")],
+                location: Location::default(),
+            }),
+            add_assign_stmt("calls", int_expr(1)),
+            return_stmt(int_expr(1)),
+        ],
+    );
+    let compound = |key: Expr| {
+        Expr::Assignment(AssignmentExpr {
+            symbol: String::from("items"),
+            path: vec![key],
+            operator: Some(BinaryOperator::Add),
+            value: Box::new(int_expr(5)),
+            location: Location::default(),
+        })
+    };
+    // items[next()] += 5
+    assert_eq!(
+        eval(compound(call_expr(next, vec![])), &ctx),
+        Ok(Value::Int(25))
+    );
+    assert_eq!(ctx.scope.get("calls"), Value::Int(1));
+    // items[i += 2] += 5 reads and writes the same element
+    let side_effect_key = Expr::Assignment(AssignmentExpr {
+        symbol: String::from("i"),
+        path: vec![],
+        operator: Some(BinaryOperator::Add),
+        value: Box::new(int_expr(2)),
+        location: Location::default(),
+    });
+    assert_eq!(eval(compound(side_effect_key), &ctx), Ok(Value::Int(35)));
+    assert_eq!(ctx.scope.get("i"), Value::Int(2));
+    assert_eq!(
+        ctx.scope.get("items"),
+        Value::vector(vec![Value::Int(10), Value::Int(25), Value::Int(35)])
+    );
+}
+#[test]
+fn test_nested_map_assignment() {
+    let ctx = Context::new(HashScope::default());
+    let scores = Value::map(std::collections::HashMap::from([(
+        String::from("ana"),
+        Value::Int(1),
+    )]));
+    ctx.scope.set(
+        "players",
+        Value::map(std::collections::HashMap::from([(
+            String::from("scores"),
+            scores,
+        )])),
+    );
+    // Existing keys are replaced and missing keys are inserted at the end of the path
+    eval(
+        path_assign_expr(
+            "players",
+            vec![str_expr("scores"), str_expr("bob")],
+            int_expr(2),
+            Location::default(),
+        ),
+        &ctx,
+    )
+    .unwrap();
+    assert_eq!(
+        eval(
+            access_expr(
+                access_expr(
+                    symbol_expr("players"),
+                    str_expr("scores"),
+                    Location::default()
+                ),
+                str_expr("bob"),
+                Location::default()
+            ),
+            &ctx
+        ),
+        Ok(Value::Int(2))
+    );
+    assert_eq!(
+        eval(
+            path_assign_expr(
+                "players",
+                vec![
+                    Expr::Literal(Literal::String(Str {
+                        value: String::from("teams"),
+                        location: Location::new(8, 13),
+                    })),
+                    str_expr("red"),
+                ],
+                int_expr(2),
+                Location::new(0, 22),
+            ),
+            &ctx,
+        ),
+        Err(
+            DashlangError::new("Key 'teams' not found in map", ErrorKind::MissingKey)
+                .location(Location::new(0, 13))
+        )
+    );
+}
+#[test]
+fn test_assign_to_tuple_element() {
+    let ctx = Context::new(HashScope::default());
+    ctx.scope
+        .set("point", Value::Tuple(vec![Value::Int(1), Value::Int(2)]));
+    assert_eq!(
+        eval(
+            path_assign_expr(
+                "point",
+                vec![Expr::Literal(Literal::Int(Int {
+                    value: 0,
+                    location: Location::new(6, 7),
+                }))],
+                int_expr(5),
+                Location::new(0, 11)
+            ),
+            &ctx,
+        ),
+        Err(DashlangError::new(
+            "Only vector elements and map fields can be assigned",
+            ErrorKind::InvalidOperation
+        )
+        .location(Location::new(0, 7)))
+    );
+}
+#[test]
+fn test_call_closure_expression() {
+    let ctx = Context::new(HashScope::default());
+    let identity = Expr::Literal(Literal::Closure(Closure {
+        params: vec![binding("x")],
+        body: vec![return_stmt(symbol_expr("x"))],
+        location: Location::default(),
+    }));
+    let call = Expr::Call(Call {
+        callee: Box::new(identity),
+        args: vec![int_expr(5)],
+        location: Location::default(),
+    });
+    assert_eq!(eval(call, &ctx), Ok(Value::Int(5)));
+}
+#[test]
+fn test_call_non_callable_expression() {
+    let ctx = Context::new(HashScope::default());
+    let call = Expr::Call(Call {
+        callee: Box::new(Expr::Literal(Literal::Int(Int {
+            value: 1,
+            location: Location::new(0, 1),
+        }))),
+        args: vec![],
+        location: Location::new(0, 3),
+    });
+    assert_eq!(
+        eval(call, &ctx),
+        Err(DashlangError::new(
+            "Cannot call expression: not callable",
+            ErrorKind::NonCallable
+        )
+        .location(Location::new(0, 1)))
+    );
+}
+#[test]
+fn test_closure_captures_factory_scope() {
+    let ctx = Context::new(HashScope::default());
+    // make_adder = (n) { return (x) { return x + n } }
+    // add_one = make_adder(1)
+    // n = 100
+    // return add_one(2)
+    let program: Program = vec![
+        assign_stmt(
+            "make_adder",
+            closure_expr(
+                &["n"],
+                vec![return_stmt(closure_expr(
+                    &["x"],
+                    vec![return_stmt(add_expr(symbol_expr("x"), symbol_expr("n")))],
+                ))],
+            ),
+        ),
+        assign_stmt(
+            "add_one",
+            call_expr(symbol_expr("make_adder"), vec![int_expr(1)]),
+        ),
+        assign_stmt("n", int_expr(100)),
+        return_stmt(call_expr(symbol_expr("add_one"), vec![int_expr(2)])),
+    ];
+    assert_eq!(eval_program(program, &ctx), Ok(Value::Int(3)));
+}
+#[test]
+fn test_closure_with_foreign_environment() {
+    let ctx = Context::new(HashScope::default());
+    ctx.scope.set(
+        "f",
+        Value::Closure(value::Closure {
+            params: vec![],
+            body: vec![return_stmt(int_expr(1))],
+            env: Environment(Rc::new(())),
+        }),
+    );
+    assert_eq!(
+        eval(call_expr(symbol_expr("f"), vec![]), &ctx),
+        Err(DashlangError::new(
+            "Cannot call 'f': it was created with another kind of scope",
+            ErrorKind::Unknown
+        )
+        .location(Location::default()))
+    );
+}
+#[test]
+fn test_counters_keep_their_own_state() {
+    let ctx = Context::new(HashScope::default());
+    // make_counter = (count) { return () { return count + 1 } }
+    // first = make_counter(0)
+    // second = make_counter(10)
+    // count = 100
+    let program: Program = vec![
+        assign_stmt(
+            "make_counter",
+            closure_expr(
+                &["count"],
+                vec![return_stmt(closure_expr(
+                    &[],
+                    vec![return_stmt(add_expr(symbol_expr("count"), int_expr(1)))],
+                ))],
+            ),
+        ),
+        assign_stmt(
+            "first",
+            call_expr(symbol_expr("make_counter"), vec![int_expr(0)]),
+        ),
+        assign_stmt(
+            "second",
+            call_expr(symbol_expr("make_counter"), vec![int_expr(10)]),
+        ),
+        assign_stmt("count", int_expr(100)),
+    ];
+    eval_program(program, &ctx).unwrap();
+    assert_eq!(
+        eval(call_expr(symbol_expr("first"), vec![]), &ctx),
+        Ok(Value::Int(1))
+    );
+    assert_eq!(
+        eval(call_expr(symbol_expr("second"), vec![]), &ctx),
+        Ok(Value::Int(11))
+    );
+}
+#[test]
+fn test_callback_sees_its_defining_scope() {
+    let ctx = Context::new(HashScope::default());
+    // map = (items, f) {
+    //   result = [0, 0]
+    //   for i = 0; i < 2; i += 1 { result[i] = f(items[i]) }
+    //   return result
+    // }
+    // i = 10
+    // return map([1, 2], (x) { return x + i })
+    let map_body: Program = vec![
+        assign_stmt(
+            "result",
+            Expr::Literal(Literal::Vector(ast::Vector {
+                value: vec![int_expr(0), int_expr(0)],
+                location: Default::default(),
+            })),
+        ),
+        Stmt::For(Box::new(For {
+            label: None,
+            init: assign_stmt("i", int_expr(0)),
+            cond: Expr::BinaryExpr(Box::new(BinaryExpr::new(
+                symbol_expr("i"),
+                int_expr(2),
+                BinaryOperator::Lt,
+            ))),
+            iteration: add_assign_stmt("i", int_expr(1)),
+            body: vec![Stmt::Expr(path_assign_expr(
+                "result",
+                vec![symbol_expr("i")],
+                call_expr(
+                    symbol_expr("f"),
+                    vec![access_expr(
+                        symbol_expr("items"),
+                        symbol_expr("i"),
+                        Location::default(),
+                    )],
+                ),
+                Location::default(),
+            ))],
+            location: Location::default(),
+        })),
+        return_stmt(symbol_expr("result")),
+    ];
+    let program: Program = vec![
+        assign_stmt("map", closure_expr(&["items", "f"], map_body)),
+        assign_stmt("i", int_expr(10)),
+        return_stmt(call_expr(
+            symbol_expr("map"),
+            vec![
+                Expr::Literal(Literal::Vector(ast::Vector {
+                    value: vec![int_expr(1), int_expr(2)],
+                    location: Default::default(),
+                })),
+                closure_expr(
+                    &["x"],
+                    vec![return_stmt(add_expr(symbol_expr("x"), symbol_expr("i")))],
+                ),
+            ],
+        )),
+    ];
+    assert_eq!(
+        eval_program(program, &ctx),
+        Ok(Value::vector(vec![Value::Int(11), Value::Int(12)]))
+    );
+}
+#[test]
+fn test_closure_mutates_captured_counter() {
+    let ctx = Context::new(HashScope::default());
+    // make_counter = () {
+    //   count = 0
+    //   return () { nonlocal count count += 1 return count }
+    // }
+    // counter = make_counter()
+    // counter()
+    // return counter()
+    let program: Program = vec![
+        assign_stmt(
+            "make_counter",
+            closure_expr(
+                &[],
+                vec![
+                    assign_stmt("count", int_expr(0)),
+                    return_stmt(closure_expr(
+                        &[],
+                        vec![
+                            Stmt::Nonlocal(Nonlocal {
+                                symbols: vec![String::from("count")],
+                                location: Location::default(),
+                            }),
+                            add_assign_stmt("count", int_expr(1)),
+                            return_stmt(symbol_expr("count")),
+                        ],
+                    )),
+                ],
+            ),
+        ),
+        assign_stmt("counter", call_expr(symbol_expr("make_counter"), vec![])),
+        Stmt::Expr(call_expr(symbol_expr("counter"), vec![])),
+        return_stmt(call_expr(symbol_expr("counter"), vec![])),
+    ];
+    assert_eq!(eval_program(program, &ctx), Ok(Value::Int(2)));
+}
+#[test]
+fn test_closure_assignment_shadows_outer_variable() {
+    let ctx = Context::new(HashScope::default());
+    // count = 1
+    // reset = () { count = 0 }
+    // reset()
+    // return count
+    let program: Program = vec![
+        assign_stmt("count", int_expr(1)),
+        assign_stmt(
+            "reset",
+            closure_expr(&[], vec![assign_stmt("count", int_expr(0))]),
+        ),
+        Stmt::Expr(call_expr(symbol_expr("reset"), vec![])),
+        return_stmt(symbol_expr("count")),
+    ];
+    assert_eq!(eval_program(program, &ctx), Ok(Value::Int(1)));
+}
+#[test]
+fn test_nonlocal_without_binding() {
+    let ctx = Context::new(HashScope::default());
+    let program: Program = vec![Stmt::Nonlocal(Nonlocal {
+        symbols: vec![String::from("count")],
+        location: Location::new(0, 14),
+    })];
+    assert_eq!(
+        eval_program(program, &ctx),
+        Err(
+            DashlangError::new("No binding for nonlocal 'count' found", ErrorKind::Unknown)
+                .location(Location::new(0, 14))
+        )
+    );
+}
+#[test]
+fn test_integer_errors_instead_of_panics() {
+    let ctx = Context::new(HashScope::default());
+    let operation = |left: i64, right: i64, operator: BinaryOperator| {
+        Expr::BinaryExpr(Box::new(BinaryExpr::new(
+            int_expr(left),
+            int_expr(right),
+            operator,
+        )))
+    };
+    assert_eq!(
+        eval(operation(1, 0, BinaryOperator::Div), &ctx),
+        Err(
+            DashlangError::new("Division by zero", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+    assert_eq!(
+        eval(operation(i64::MAX, 1, BinaryOperator::Add), &ctx),
+        Err(
+            DashlangError::new("Integer overflow", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+    assert_eq!(
+        eval(operation(i64::MIN, -1, BinaryOperator::Div), &ctx),
+        Err(
+            DashlangError::new("Integer overflow", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+    assert_eq!(
+        eval(operation(1, 64, BinaryOperator::BitwiseShiftLeft), &ctx),
+        Err(
+            DashlangError::new("Shift amount out of range", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+    for (left, shift) in [(i64::MAX, 1), (3, 62), (-3, 63)] {
+        assert_eq!(
+            eval(
+                operation(left, shift, BinaryOperator::BitwiseShiftLeft),
+                &ctx
+            ),
+            Err(
+                DashlangError::new("Integer overflow", ErrorKind::Arithmetic)
+                    .location(Location::default())
+            )
+        );
+    }
+    assert_eq!(
+        eval(operation(-1, 63, BinaryOperator::BitwiseShiftLeft), &ctx),
+        Ok(Value::Int(i64::MIN))
+    );
+    assert_eq!(
+        eval(operation(i64::MIN, -1, BinaryOperator::Mod), &ctx),
+        Ok(Value::Int(0))
+    );
+}
+#[test]
+fn test_modulo() {
+    let ctx = Context::new(HashScope::default());
+    let modulo = |left, right| eval(binary_expr(left, right, BinaryOperator::Mod), &ctx);
+    assert_eq!(modulo(int_expr(7), int_expr(3)), Ok(int_value(1)));
+    assert_eq!(modulo(int_expr(-7), int_expr(3)), Ok(int_value(-1)));
+    assert_eq!(modulo(float_expr(7.5), int_expr(2)), Ok(float_value(1.5)));
+    assert_eq!(
+        modulo(int_expr(7), int_expr(0)),
+        Err(
+            DashlangError::new("Division by zero", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+}
+#[test]
+fn test_pow() {
+    let ctx = Context::new(HashScope::default());
+    let pow = |left, right| eval(binary_expr(left, right, BinaryOperator::Pow), &ctx);
+    assert_eq!(pow(int_expr(2), int_expr(10)), Ok(int_value(1024)));
+    assert_eq!(pow(int_expr(2), int_expr(-1)), Ok(float_value(0.5)));
+    assert_eq!(pow(float_expr(9.0), float_expr(0.5)), Ok(float_value(3.0)));
+    assert_eq!(
+        pow(int_expr(2), int_expr(64)),
+        Err(
+            DashlangError::new("Integer overflow", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+}
+#[test]
+fn test_int_div() {
+    let ctx = Context::new(HashScope::default());
+    let int_div = |left, right| eval(binary_expr(left, right, BinaryOperator::IntDiv), &ctx);
+    assert_eq!(int_div(int_expr(7), int_expr(2)), Ok(int_value(3)));
+    assert_eq!(int_div(float_expr(7.5), int_expr(2)), Ok(int_value(3)));
+    assert_eq!(int_div(int_expr(-7), float_expr(2.0)), Ok(int_value(-3)));
+    assert_eq!(
+        int_div(float_expr(1.0), float_expr(0.0)),
+        Err(
+            DashlangError::new("Division by zero", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+    assert_eq!(
+        int_div(float_expr(1e300), int_expr(1)),
+        Err(
+            DashlangError::new("Integer overflow", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+}
+#[test]
+fn test_not_equal() {
+    let ctx = Context::new(HashScope::default());
+    let ne = |left, right| eval(binary_expr(left, right, BinaryOperator::Ne), &ctx);
+    assert_eq!(ne(int_expr(1), int_expr(2)), Ok(Value::Bool(true)));
+    assert_eq!(ne(int_expr(1), int_expr(1)), Ok(Value::Bool(false)));
+    assert_eq!(ne(str_expr("a"), int_expr(1)), Ok(Value::Bool(true)));
+}
+#[test]
+fn test_unary_neg() {
+    let ctx = Context::new(HashScope::default());
+    let neg = |operand| {
+        eval(
+            Expr::UnaryExpr(Box::new(UnaryExpr {
+                operator: ast::UnaryOperator::Neg,
+                operand,
+                location: Location::default(),
+            })),
+            &ctx,
+        )
+    };
+    assert_eq!(neg(int_expr(5)), Ok(int_value(-5)));
+    assert_eq!(neg(float_expr(-1.5)), Ok(float_value(1.5)));
+    assert_eq!(
+        neg(int_expr(i64::MIN)),
+        Err(
+            DashlangError::new("Integer overflow", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+    assert_eq!(
+        neg(str_expr("a")),
+        Err(
+            DashlangError::new("Expected a number", ErrorKind::InvalidOperation)
+                .location(Location::default())
+        )
+    );
+}
+#[test]
+fn test_logical_operators_short_circuit() {
+    let ctx = Context::new(HashScope::default());
+    // Calling an undefined symbol fails, so these only succeed when the right side is skipped
+    let failing_call = || call_expr(symbol_expr("missing"), vec![]);
+    assert_eq!(
+        eval(
+            binary_expr(bool_expr(false), failing_call(), BinaryOperator::And),
+            &ctx
+        ),
+        Ok(Value::Bool(false))
+    );
+    assert_eq!(
+        eval(
+            binary_expr(int_expr(1), failing_call(), BinaryOperator::Or),
+            &ctx
+        ),
+        Ok(Value::Bool(true))
+    );
+    assert!(eval(
+        binary_expr(bool_expr(true), failing_call(), BinaryOperator::And),
+        &ctx
+    )
+    .is_err());
+    assert_eq!(
+        eval(
+            binary_expr(null_expr(), str_expr("text"), BinaryOperator::Or),
+            &ctx
+        ),
+        Ok(Value::Bool(true))
+    );
+}
+#[test]
+fn test_short_circuit_skips_side_effects() {
+    let ctx = Context::new(HashScope::default());
+    let program = vec![
+        assign_stmt("count", int_expr(0)),
+        assign_stmt(
+            "bump",
+            closure_expr(
+                &[],
+                vec![
+                    Stmt::Nonlocal(Nonlocal {
+                        symbols: vec![String::from("count")],
+                        location: Location::default(),
+                    }),
+                    add_assign_stmt("count", int_expr(1)),
+                    return_stmt(bool_expr(true)),
+                ],
+            ),
+        ),
+        Stmt::Expr(binary_expr(
+            bool_expr(true),
+            call_expr(symbol_expr("bump"), vec![]),
+            BinaryOperator::Or,
+        )),
+        Stmt::Expr(binary_expr(
+            bool_expr(true),
+            call_expr(symbol_expr("bump"), vec![]),
+            BinaryOperator::And,
+        )),
+    ];
+    ctx.run_program(program).unwrap();
+    assert_eq!(ctx.scope.get("count"), int_value(1));
+}
+#[test]
+fn test_structural_equality() {
+    let ctx = Context::new(HashScope::default());
+    let equal = |left, right| {
+        eval(binary_expr(left, right, BinaryOperator::Eq), &ctx).map(|result| result.is_truthy())
+    };
+    assert_eq!(equal(str_expr("a"), str_expr("a")), Ok(true));
+    assert_eq!(equal(str_expr("a"), str_expr("b")), Ok(false));
+    assert_eq!(equal(int_expr(1), float_expr(1.0)), Ok(true));
+    assert_eq!(equal(float_expr(1.5), int_expr(1)), Ok(false));
+    assert_eq!(
+        equal(
+            vector_expr(vec![int_expr(1), add_expr(int_expr(1), int_expr(1))]),
+            vector_expr(vec![int_expr(1), float_expr(2.0)])
+        ),
+        Ok(true)
+    );
+    assert_eq!(
+        equal(
+            vector_expr(vec![int_expr(1)]),
+            vector_expr(vec![int_expr(1), int_expr(2)])
+        ),
+        Ok(false)
+    );
+    assert_eq!(
+        equal(
+            tuple_expr(vec![str_expr("a"), vector_expr(vec![])]),
+            tuple_expr(vec![str_expr("a"), vector_expr(vec![])])
+        ),
+        Ok(true)
+    );
+    assert_eq!(
+        equal(
+            map_expr(vec![("a", int_expr(1)), ("b", str_expr("x"))]),
+            map_expr(vec![("b", str_expr("x")), ("a", int_expr(1))])
+        ),
+        Ok(true)
+    );
+    assert_eq!(
+        equal(
+            map_expr(vec![("a", int_expr(1))]),
+            map_expr(vec![("b", int_expr(1))])
+        ),
+        Ok(false)
+    );
+    assert_eq!(equal(vector_expr(vec![]), tuple_expr(vec![])), Ok(false));
+    assert_eq!(equal(null_expr(), bool_expr(false)), Ok(false));
+}
+#[test]
+fn test_ordering() {
+    let ctx = Context::new(HashScope::default());
+    let less = |left, right| {
+        eval(binary_expr(left, right, BinaryOperator::Lt), &ctx).map(|result| result.is_truthy())
+    };
+    assert_eq!(less(str_expr("apple"), str_expr("banana")), Ok(true));
+    assert_eq!(less(str_expr("b"), str_expr("abc")), Ok(false));
+    assert_eq!(less(int_expr(1), float_expr(1.5)), Ok(true));
+    // Rounding i64::MAX to a float would make these equal
+    assert_eq!(
+        less(int_expr(i64::MAX - 1), float_expr(i64::MAX as f64)),
+        Ok(true)
+    );
+    assert_eq!(
+        less(
+            vector_expr(vec![int_expr(1), int_expr(2)]),
+            vector_expr(vec![int_expr(1), int_expr(3)])
+        ),
+        Ok(true)
+    );
+    assert_eq!(
+        less(
+            vector_expr(vec![int_expr(1)]),
+            vector_expr(vec![int_expr(1), int_expr(0)])
+        ),
+        Ok(true)
+    );
+    assert_eq!(
+        less(
+            tuple_expr(vec![int_expr(2), str_expr("a")]),
+            tuple_expr(vec![int_expr(1), str_expr("b")])
+        ),
+        Ok(false)
+    );
+}
+#[test]
+fn test_ordering_incomparable_values() {
+    let ctx = Context::new(HashScope::default());
+    let location = Location::new(3, 9);
+    let greater = |left, right| {
+        eval(
+            Expr::BinaryExpr(Box::new(BinaryExpr {
+                left,
+                right,
+                operator: BinaryOperator::Gt,
+                location,
+            })),
+            &ctx,
+        )
+    };
+    assert_eq!(
+        greater(str_expr("a"), int_expr(1)),
+        Err(DashlangError::new(
+            "Cannot compare string with integer",
+            ErrorKind::InvalidOperation
+        )
+        .location(location))
+    );
+    assert_eq!(
+        greater(map_expr(vec![]), map_expr(vec![])),
+        Err(
+            DashlangError::new("Cannot compare map with map", ErrorKind::InvalidOperation)
+                .location(location)
+        )
+    );
+    assert_eq!(
+        greater(
+            vector_expr(vec![int_expr(1), null_expr()]),
+            vector_expr(vec![int_expr(1), int_expr(2)])
+        ),
+        Err(DashlangError::new(
+            "Cannot compare null with integer",
+            ErrorKind::InvalidOperation
+        )
+        .location(location))
+    );
+}
+#[test]
+fn test_big_ints() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_big_ints();
+    let operation = |left, right, operator| eval(binary_expr(left, right, operator), &ctx);
+    let max = || int_expr(i64::MAX);
+    assert_eq!(
+        operation(max(), int_expr(1), BinaryOperator::Add),
+        Ok(big_int("9223372036854775808"))
+    );
+    assert_eq!(
+        operation(int_expr(2), int_expr(100), BinaryOperator::Pow),
+        Ok(big_int("1267650600228229401496703205376"))
+    );
+    // Results that fit go back to regular integers
+    assert_eq!(
+        operation(
+            binary_expr(max(), int_expr(10), BinaryOperator::Mul),
+            int_expr(10),
+            BinaryOperator::Div
+        ),
+        Ok(int_value(i64::MAX))
+    );
+    assert_eq!(
+        operation(
+            binary_expr(max(), max(), BinaryOperator::Add),
+            float_expr(0.5),
+            BinaryOperator::Mul
+        ),
+        Ok(float_value(i64::MAX as f64))
+    );
+    assert_eq!(
+        operation(
+            binary_expr(max(), int_expr(1), BinaryOperator::Add),
+            max(),
+            BinaryOperator::Gt
+        ),
+        Ok(Value::Bool(true))
+    );
+    assert_eq!(
+        operation(
+            binary_expr(max(), int_expr(1), BinaryOperator::Add),
+            int_expr(0),
+            BinaryOperator::Mod
+        ),
+        Err(
+            DashlangError::new("Division by zero", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+    assert_eq!(
+        eval(
+            Expr::UnaryExpr(Box::new(UnaryExpr {
+                operator: ast::UnaryOperator::Neg,
+                operand: int_expr(i64::MIN),
+                location: Location::default(),
+            })),
+            &ctx
+        ),
+        Ok(big_int("9223372036854775808"))
+    );
+}
+#[test]
+fn test_big_ints_are_limited_in_size() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_big_ints();
+    let operation = |left, right, operator| eval(binary_expr(left, right, operator), &ctx);
+    let too_large = Err(
+        DashlangError::new("Integer too large", ErrorKind::Arithmetic)
+            .location(Location::default()),
+    );
+    assert_eq!(
+        operation(
+            int_expr(1),
+            int_expr(100000000000),
+            BinaryOperator::BitwiseShiftLeft
+        ),
+        too_large
+    );
+    assert_eq!(
+        operation(int_expr(2), int_expr(3000000000), BinaryOperator::Pow),
+        too_large
+    );
+    // Powers of 1, 0 and -1 never grow
+    assert_eq!(
+        operation(int_expr(-1), int_expr(3000000001), BinaryOperator::Pow),
+        Ok(int_value(-1))
+    );
+    assert_eq!(
+        operation(
+            int_expr(0),
+            int_expr(100000000000),
+            BinaryOperator::BitwiseShiftLeft
+        ),
+        Ok(int_value(0))
+    );
+    assert!(matches!(
+        operation(
+            int_expr(1),
+            int_expr(4000000),
+            BinaryOperator::BitwiseShiftLeft
+        ),
+        Ok(Value::BigInt(_))
+    ));
+}
+#[test]
+fn test_collection_elements_evaluate_once() {
+    let ctx = Context::new(HashScope::default());
+    let program = vec![
+        assign_stmt("count", int_expr(0)),
+        assign_stmt(
+            "bump",
+            closure_expr(
+                &[],
+                vec![
+                    Stmt::Nonlocal(Nonlocal {
+                        symbols: vec![String::from("count")],
+                        location: Location::default(),
+                    }),
+                    add_assign_stmt("count", int_expr(1)),
+                    return_stmt(symbol_expr("count")),
+                ],
+            ),
+        ),
+        assign_stmt(
+            "items",
+            vector_expr(vec![call_expr(symbol_expr("bump"), vec![])]),
+        ),
+        assign_stmt(
+            "first",
+            access_expr(symbol_expr("items"), int_expr(0), Location::default()),
+        ),
+        Stmt::Expr(binary_expr(
+            symbol_expr("items"),
+            symbol_expr("items"),
+            BinaryOperator::Eq,
+        )),
+        return_stmt(access_expr(
+            symbol_expr("items"),
+            int_expr(0),
+            Location::default(),
+        )),
+    ];
+    assert_eq!(ctx.run_program(program), Ok(int_value(1)));
+    assert_eq!(ctx.scope.get("first"), int_value(1));
+    assert_eq!(ctx.scope.get("count"), int_value(1));
+}
+#[test]
+fn test_map_set_stores_evaluated_value() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_plugin(stdlib::Stdlib::new());
+    let result = eval(
+        call_expr(
+            symbol_expr("map_set"),
+            vec![
+                map_expr(vec![]),
+                str_expr("total"),
+                add_expr(int_expr(1), int_expr(1)),
+            ],
+        ),
+        &ctx,
+    );
+    assert_eq!(
+        result,
+        Ok(Value::map(std::collections::HashMap::from([(
+            String::from("total"),
+            Value::Int(2)
+        )])))
+    );
+}
+#[test]
+fn test_collections_are_shared() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_plugin(stdlib::Stdlib::new());
+    let program = vec![
+        assign_stmt("items", vector_expr(vec![int_expr(1)])),
+        assign_stmt("alias", symbol_expr("items")),
+        assign_stmt(
+            "add",
+            closure_expr(
+                &["target"],
+                vec![Stmt::Expr(call_expr(
+                    symbol_expr("push"),
+                    vec![symbol_expr("target"), int_expr(2)],
+                ))],
+            ),
+        ),
+        Stmt::Expr(call_expr(symbol_expr("add"), vec![symbol_expr("alias")])),
+        Stmt::Expr(path_assign_expr(
+            "alias",
+            vec![int_expr(0)],
+            int_expr(5),
+            Location::default(),
+        )),
+    ];
+    ctx.run_program(program).unwrap();
+    assert_eq!(
+        ctx.scope.get("items"),
+        Value::vector(vec![Value::Int(5), Value::Int(2)])
+    );
+}
+#[test]
+fn test_in_place_collection_builtins() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_plugin(stdlib::Stdlib::new());
+    let builtin = |name: &str, args| eval(call_expr(symbol_expr(name), args), &ctx);
+    ctx.scope.set(
+        "items",
+        Value::vector(vec![Value::Int(1), Value::Int(2), Value::Int(3)]),
+    );
+    assert_eq!(
+        builtin("pop", vec![symbol_expr("items")]),
+        Ok(Value::Int(3))
+    );
+    builtin(
+        "insert",
+        vec![symbol_expr("items"), int_expr(0), int_expr(0)],
+    )
+    .unwrap();
+    assert_eq!(
+        builtin("remove", vec![symbol_expr("items"), int_expr(1)]),
+        Ok(Value::Int(1))
+    );
+    assert_eq!(
+        ctx.scope.get("items"),
+        Value::vector(vec![Value::Int(0), Value::Int(2)])
+    );
+    assert_eq!(
+        builtin(
+            "insert",
+            vec![symbol_expr("items"), int_expr(3), int_expr(1)]
+        ),
+        Err(DashlangError::new(
+            "Index out of bounds: the length is 2 but the index is 3",
+            ErrorKind::OutOfBounds
+        )
+        .location(Location::default()))
+    );
+    assert_eq!(
+        builtin("pop", vec![vector_expr(vec![])]),
+        Err(
+            DashlangError::new("Cannot pop from an empty vector", ErrorKind::OutOfBounds)
+                .location(Location::default())
+        )
+    );
+    ctx.scope.set("person", Value::map(Default::default()));
+    builtin(
+        "insert",
+        vec![symbol_expr("person"), str_expr("name"), str_expr("Ana")],
+    )
+    .unwrap();
+    assert_eq!(
+        builtin("remove", vec![symbol_expr("person"), str_expr("name")]),
+        Ok(Value::String(String::from("Ana")))
+    );
+    assert_eq!(
+        builtin("remove", vec![symbol_expr("person"), str_expr("name")]),
+        Err(
+            DashlangError::new("Key 'name' not found in map", ErrorKind::MissingKey)
+                .location(Location::default())
+        )
+    );
+}
+#[test]
+fn test_len_counts_chars() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_plugin(stdlib::Stdlib::new());
+    let len = |value| eval(call_expr(symbol_expr("len"), vec![value]), &ctx);
+    assert_eq!(len(str_expr("héllo")), Ok(Value::Int(5)));
+    assert_eq!(len(str_expr("日本")), Ok(Value::Int(2)));
+    // s[len(s) - 1] is the last character
+    ctx.scope.set("s", Value::String(String::from("café")));
+    let last = access_expr(
+        symbol_expr("s"),
+        binary_expr(
+            call_expr(symbol_expr("len"), vec![symbol_expr("s")]),
+            int_expr(1),
+            BinaryOperator::Sub,
+        ),
+        Location::default(),
+    );
+    assert_eq!(eval(last, &ctx), Ok(Value::String(String::from("é"))));
+}
+#[test]
+fn test_huge_ranges() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_plugin(stdlib::Stdlib::new());
+    let full_range = || range_expr(int_expr(i64::MIN), int_expr(i64::MAX), None, false);
+    let call = |name, ctx: &Context<HashScope>| {
+        eval(call_expr(symbol_expr(name), vec![full_range()]), ctx)
+    };
+    assert_eq!(
+        call("len", &ctx),
+        Err(
+            DashlangError::new("Integer overflow", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+    assert_eq!(
+        call("to_vector", &ctx),
+        Err(DashlangError::new(
+            "Cannot make a vector of 18446744073709551615 elements: not enough memory",
+            ErrorKind::InvalidOperation
+        )
+        .location(Location::default()))
+    );
+    ctx.use_big_ints();
+    assert_eq!(call("len", &ctx), Ok(big_int("18446744073709551615")));
+}
+#[test]
+fn test_builtin_argument_errors_are_located() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_plugin(stdlib::Stdlib::new());
+    let call = |name, args| {
+        Expr::Call(Call {
+            callee: Box::new(symbol_expr(name)),
+            args,
+            location: Location::new(3, 9),
+        })
+    };
+    assert_eq!(
+        eval(call("len", vec![]), &ctx),
+        Err(DashlangError::new(
+            "Expected 'item' arg, but none was provided",
+            ErrorKind::WrongArgs
+        )
+        .location(Location::new(3, 9)))
+    );
+    assert_eq!(
+        eval(call("push", vec![vector_expr(vec![])]), &ctx),
+        Err(DashlangError::new(
+            "Expected 'item' arg, but none was provided",
+            ErrorKind::WrongArgs
+        )
+        .location(Location::new(3, 9)))
+    );
+    let located_int = Expr::Literal(Literal::Int(Int {
+        value: 1,
+        location: Location::new(4, 5),
+    }));
+    assert_eq!(
+        eval(
+            call("map_set", vec![located_int, str_expr("a"), int_expr(2)]),
+            &ctx
+        ),
+        Err(
+            DashlangError::new("Expected argument to be a map", ErrorKind::WrongArgs)
+                .location(Location::new(4, 5))
+        )
+    );
+}
+#[test]
+fn test_copy_and_deep_copy() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_plugin(stdlib::Stdlib::new());
+    let inner = Value::vector(vec![Value::Int(1)]);
+    ctx.scope.set("nested", Value::vector(vec![inner.clone()]));
+    let copy = eval(
+        call_expr(symbol_expr("copy"), vec![symbol_expr("nested")]),
+        &ctx,
+    )
+    .unwrap();
+    let deep_copy = eval(
+        call_expr(symbol_expr("deep_copy"), vec![symbol_expr("nested")]),
+        &ctx,
+    )
+    .unwrap();
+    let Value::Vector(inner_vector) = &inner else {
+        unreachable!()
+    };
+    inner_vector.borrow_mut().push(Value::Int(2));
+    let Value::Vector(nested) = ctx.scope.get("nested") else {
+        unreachable!()
+    };
+    nested.borrow_mut().push(Value::Null);
+    // The copy has its own outer vector, but shares the inner one
+    assert_eq!(
+        copy,
+        Value::vector(vec![Value::vector(vec![Value::Int(1), Value::Int(2)])])
+    );
+    assert_eq!(
+        deep_copy,
+        Value::vector(vec![Value::vector(vec![Value::Int(1)])])
+    );
+}
+#[test]
+fn test_values_containing_themselves() {
+    let cyclic = || {
+        let vector = Value::vector(vec![Value::Int(1)]);
+        let Value::Vector(items) = &vector else {
+            unreachable!()
+        };
+        items.borrow_mut().push(vector.clone());
+        vector
+    };
+    let (first, second) = (cyclic(), cyclic());
+    assert_eq!(stdlib_literal_display(&first), "[1, [...]]");
+    assert!(binary_expr::values_equal(&first, &first));
+    assert!(binary_expr::values_equal(&first, &second));
+    assert_eq!(
+        binary_expr::compare_values(&first, &second),
+        Ok(Ordering::Equal)
+    );
+    // The copy contains itself instead of the original
+    let copy = first.deep_copy();
+    let (Value::Vector(copy_items), Value::Vector(first_items)) = (&copy, &first) else {
+        unreachable!()
+    };
+    let Value::Vector(inner) = &copy_items.borrow()[1] else {
+        unreachable!()
+    };
+    assert!(Rc::ptr_eq(inner, copy_items));
+    assert!(!Rc::ptr_eq(inner, first_items));
+
+    let map = Value::map(std::collections::HashMap::new());
+    let Value::Map(attributes) = &map else {
+        unreachable!()
+    };
+    attributes
+        .borrow_mut()
+        .insert(String::from("me"), map.clone());
+    assert_eq!(stdlib_literal_display(&map), "{ me: {...} }");
+    assert!(binary_expr::values_equal(&map, &map.deep_copy()));
+}
+#[test]
+fn test_for_in_range() {
+    let ctx = Context::new(HashScope::default());
+    // for i in 0..5 { if i == 2 { continue } if i == 4 { break } total += i }
+    let program = vec![
+        assign_stmt("total", int_expr(0)),
+        for_in_stmt(
+            &["i"],
+            range_expr(int_expr(0), int_expr(5), None, false),
+            vec![
+                if_stmt(
+                    binary_expr(symbol_expr("i"), int_expr(2), BinaryOperator::Eq),
+                    vec![Stmt::Continue(Continue {
+                        label: None,
+                        location: Location::default(),
+                    })],
+                ),
+                if_stmt(
+                    binary_expr(symbol_expr("i"), int_expr(4), BinaryOperator::Eq),
+                    vec![Stmt::Break(Break {
+                        label: None,
+                        location: Location::default(),
+                    })],
+                ),
+                add_assign_stmt("total", symbol_expr("i")),
+            ],
+        ),
+    ];
+    ctx.run_program(program).unwrap();
+    assert_eq!(ctx.scope.get("total"), Value::Int(4));
+}
+#[test]
+fn test_for_in_collections() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_plugin(stdlib::Stdlib::new());
+    // items = [] for <symbols> in <iterable> { push(items, <symbol>)... } return items
+    let collect = |symbols: &[&str], iterable| {
+        let pushes = symbols
+            .iter()
+            .map(|symbol| {
+                Stmt::Expr(call_expr(
+                    symbol_expr("push"),
+                    vec![symbol_expr("items"), symbol_expr(symbol)],
+                ))
+            })
+            .collect();
+        ctx.run_program(vec![
+            assign_stmt("items", vector_expr(vec![])),
+            for_in_stmt(symbols, iterable, pushes),
+            return_stmt(symbol_expr("items")),
+        ])
+    };
+    let string = |value: &str| Value::String(value.to_owned());
+    assert_eq!(
+        collect(&["item"], vector_expr(vec![int_expr(1), str_expr("a")])),
+        Ok(Value::vector(vec![Value::Int(1), string("a")]))
+    );
+    assert_eq!(
+        collect(&["item"], tuple_expr(vec![int_expr(1), null_expr()])),
+        Ok(Value::vector(vec![Value::Int(1), Value::Null]))
+    );
+    assert_eq!(
+        collect(&["char"], str_expr("héy")),
+        Ok(Value::vector(vec![string("h"), string("é"), string("y")]))
+    );
+    assert_eq!(
+        collect(
+            &["key", "value"],
+            map_expr(vec![("b", int_expr(2)), ("a", int_expr(1))])
+        ),
+        Ok(Value::vector(vec![
+            string("a"),
+            Value::Int(1),
+            string("b"),
+            Value::Int(2)
+        ]))
+    );
+    assert_eq!(
+        collect(
+            &["item"],
+            Expr::Literal(Literal::Int(Int {
+                value: 3,
+                location: Location::new(9, 10),
+            }))
+        ),
+        Err(
+            DashlangError::new("Cannot iterate over integer", ErrorKind::InvalidOperation)
+                .location(Location::new(9, 10))
+        )
+    );
+}
+#[test]
+fn test_for_in_over_changing_vector() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_plugin(stdlib::Stdlib::new());
+    // Pushing to the vector being iterated doesn't make the loop go on forever
+    let program = vec![
+        assign_stmt("items", vector_expr(vec![int_expr(1), int_expr(2)])),
+        for_in_stmt(
+            &["item"],
+            symbol_expr("items"),
+            vec![Stmt::Expr(call_expr(
+                symbol_expr("push"),
+                vec![symbol_expr("items"), symbol_expr("item")],
+            ))],
+        ),
+        return_stmt(call_expr(symbol_expr("len"), vec![symbol_expr("items")])),
+    ];
+    assert_eq!(ctx.run_program(program), Ok(Value::Int(4)));
+}
+#[test]
+fn test_range_elements() {
+    let ctx = Context::new(HashScope::default());
+    let elements = |start, end, step: Option<i64>, inclusive| {
+        let range = range_expr(
+            int_expr(start),
+            int_expr(end),
+            step.map(int_expr),
+            inclusive,
+        );
+        match eval(range, &ctx) {
+            Ok(Value::Range(range)) => range.iter().collect::<Vec<i64>>(),
+            other => panic!("Expected a range, got {other:?}"),
+        }
+    };
+    assert_eq!(elements(0, 4, None, false), vec![0, 1, 2, 3]);
+    assert_eq!(elements(0, 4, None, true), vec![0, 1, 2, 3, 4]);
+    assert_eq!(elements(0, 10, Some(3), false), vec![0, 3, 6, 9]);
+    assert_eq!(elements(10, 0, Some(-4), true), vec![10, 6, 2]);
+    assert_eq!(elements(5, 0, None, false), Vec::<i64>::new());
+    assert_eq!(elements(0, 5, Some(-1), false), Vec::<i64>::new());
+    assert_eq!(
+        elements(i64::MAX - 1, i64::MAX, None, true),
+        vec![i64::MAX - 1, i64::MAX]
+    );
+}
+#[test]
+fn test_range_display_and_equality() {
+    let ctx = Context::new(HashScope::default());
+    let range = |start, end, step: Option<i64>, inclusive| {
+        eval(
+            range_expr(
+                int_expr(start),
+                int_expr(end),
+                step.map(int_expr),
+                inclusive,
+            ),
+            &ctx,
+        )
+        .unwrap()
+    };
+    assert_eq!(stdlib_literal_display(&range(0, 5, None, false)), "0..5");
+    assert_eq!(stdlib_literal_display(&range(0, 5, None, true)), "0..6");
+    assert_eq!(
+        stdlib_literal_display(&range(0, 9, Some(2), false)),
+        "0..10 step 2"
+    );
+    assert_eq!(
+        stdlib_literal_display(&range(0, i64::MAX, None, true)),
+        "0..=9223372036854775807"
+    );
+    // Ranges are equal when they give the same integers
+    assert!(binary_expr::values_equal(
+        &range(0, 9, Some(2), false),
+        &range(0, 8, Some(2), true)
+    ));
+    assert!(binary_expr::values_equal(
+        &range(3, 3, None, false),
+        &range(5, 0, None, false)
+    ));
+    assert!(!binary_expr::values_equal(
+        &range(0, 3, None, false),
+        &range(0, 3, None, true)
+    ));
+}
+#[test]
+fn test_range_access() {
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_plugin(stdlib::Stdlib::new());
+    let evens = range_expr(int_expr(0), int_expr(100), Some(int_expr(2)), false);
+    let slice = |start, end| range_expr(int_expr(start), int_expr(end), None, false);
+    let access = |target: Expr, key| eval(access_expr(target, key, Location::new(0, 9)), &ctx);
+    assert_eq!(
+        eval(call_expr(symbol_expr("len"), vec![evens.clone()]), &ctx),
+        Ok(Value::Int(50))
+    );
+    assert_eq!(access(evens.clone(), int_expr(3)), Ok(Value::Int(6)));
+    assert_eq!(
+        access(evens.clone(), int_expr(50)),
+        Err(DashlangError::new(
+            "Index out of bounds: the length is 50 but the index is 50",
+            ErrorKind::OutOfBounds
+        )
+        .location(Location::new(0, 9)))
+    );
+    assert_eq!(
+        access(evens.clone(), slice(10, 13)).map(|range| stdlib_literal_display(&range)),
+        Ok(String::from("20..26 step 2"))
+    );
+    assert_eq!(
+        access(
+            vector_expr(vec![int_expr(1), int_expr(2), int_expr(3)]),
+            slice(1, 3)
+        ),
+        Ok(Value::vector(vec![Value::Int(2), Value::Int(3)]))
+    );
+    assert_eq!(
+        access(
+            str_expr("héllo"),
+            range_expr(int_expr(4), int_expr(0), Some(int_expr(-2)), true)
+        ),
+        Ok(Value::String(String::from("olh")))
+    );
+    assert_eq!(
+        access(str_expr("abc"), slice(3, 3)),
+        Ok(Value::String(String::new()))
+    );
+    assert_eq!(
+        access(str_expr("abc"), slice(2, 4)),
+        Err(DashlangError::new(
+            "Slice out of bounds: the length is 3 but the range is 2..4",
+            ErrorKind::OutOfBounds
+        )
+        .location(Location::new(0, 9)))
+    );
+    assert_eq!(
+        eval(
+            call_expr(
+                symbol_expr("to_vector"),
+                vec![range_expr(
+                    int_expr(3),
+                    int_expr(0),
+                    Some(int_expr(-1)),
+                    false
+                )]
+            ),
+            &ctx
+        ),
+        Ok(Value::vector(vec![
+            Value::Int(3),
+            Value::Int(2),
+            Value::Int(1)
+        ]))
+    );
+}
+#[test]
+fn test_range_errors() {
+    let ctx = Context::new(HashScope::default());
+    let zero_step = Expr::Literal(Literal::Int(Int {
+        value: 0,
+        location: Location::new(8, 9),
+    }));
+    assert_eq!(
+        eval(
+            range_expr(int_expr(0), int_expr(5), Some(zero_step), false),
+            &ctx
+        ),
+        Err(
+            DashlangError::new("Range step can't be zero", ErrorKind::InvalidOperation)
+                .location(Location::new(8, 9))
+        )
+    );
+    assert_eq!(
+        eval(
+            range_expr(
+                int_expr(0),
+                Expr::Literal(Literal::Float(ast::Float {
+                    value: 1.5,
+                    location: Location::new(3, 6),
+                })),
+                None,
+                false
+            ),
+            &ctx
+        ),
+        Err(DashlangError::new(
+            "Expected range bounds and step to be integers",
+            ErrorKind::InvalidOperation
+        )
+        .location(Location::new(3, 6)))
+    );
+    assert_eq!(
+        eval(
+            range_expr(int_expr(i64::MIN), int_expr(i64::MAX), None, true),
+            &ctx
+        ),
+        Err(
+            DashlangError::new("Range is too long", ErrorKind::Arithmetic)
+                .location(Location::default())
+        )
+    );
+}
+#[test]
+fn test_match_tuples() {
+    let ctx = Context::new(HashScope::default());
+    // match value { (:ok, n) if n > 10 -> "big", (:ok, n) -> n, (:error, _) -> "error", _ -> null }
+    let describe = |value| {
+        let arms = vec![
+            (
+                tuple_pattern(vec![Pattern::Literal(atom_literal("ok")), binding("n")]),
+                Some(binary_expr(
+                    symbol_expr("n"),
+                    int_expr(10),
+                    BinaryOperator::Gt,
+                )),
+                str_expr("big"),
+            ),
+            (
+                tuple_pattern(vec![Pattern::Literal(atom_literal("ok")), binding("n")]),
+                None,
+                symbol_expr("n"),
+            ),
+            (
+                tuple_pattern(vec![
+                    Pattern::Literal(atom_literal("error")),
+                    Pattern::Wildcard(Location::default()),
+                ]),
+                None,
+                str_expr("error"),
+            ),
+            (Pattern::Wildcard(Location::default()), None, null_expr()),
+        ];
+        eval(match_expr(value, arms), &ctx)
+    };
+    let ok = |value| tuple_expr(vec![Expr::Literal(atom_literal("ok")), value]);
+    assert_eq!(describe(ok(int_expr(3))), Ok(Value::Int(3)));
+    assert_eq!(
+        describe(ok(int_expr(30))),
+        Ok(Value::String(String::from("big")))
+    );
+    assert_eq!(
+        describe(tuple_expr(vec![
+            Expr::Literal(atom_literal("error")),
+            str_expr("oops")
+        ])),
+        Ok(Value::String(String::from("error")))
+    );
+    // Tuples only match patterns with the same number of elements
+    assert_eq!(
+        describe(tuple_expr(vec![
+            Expr::Literal(atom_literal("ok")),
+            int_expr(1),
+            int_expr(2)
+        ])),
+        Ok(Value::Null)
+    );
+}
+#[test]
+fn test_failed_guard_leaves_scope_alone() {
+    let ctx = Context::new(HashScope::default());
+    ctx.scope.set("x", Value::Int(5));
+    // match (1, 2) { (x, y) if x > 10 -> 0, _ -> x }
+    let arms = vec![
+        (
+            tuple_pattern(vec![binding("x"), binding("y")]),
+            Some(binary_expr(
+                symbol_expr("x"),
+                int_expr(10),
+                BinaryOperator::Gt,
+            )),
+            int_expr(0),
+        ),
+        (
+            Pattern::Wildcard(Location::default()),
+            None,
+            symbol_expr("x"),
+        ),
+    ];
+    let value = tuple_expr(vec![int_expr(1), int_expr(2)]);
+    assert_eq!(eval(match_expr(value, arms), &ctx), Ok(Value::Int(5)));
+    assert_eq!(ctx.scope.get("x"), Value::Int(5));
+    assert_eq!(ctx.scope.get("y"), Value::Void);
+}
+#[test]
+fn test_match_vectors_and_maps() {
+    let ctx = Context::new(HashScope::default());
+    // match value { [] -> 0, [first, ...rest] -> (first, rest), {name, age: 18} -> name, _ -> null }
+    let describe = |value| {
+        let arms = vec![
+            (
+                Pattern::Vector(VectorPattern {
+                    items: vec![],
+                    rest: None,
+                    location: Location::default(),
+                }),
+                None,
+                int_expr(0),
+            ),
+            (
+                Pattern::Vector(VectorPattern {
+                    items: vec![binding("first")],
+                    rest: Some(RestPattern {
+                        symbol: Some(Symbol {
+                            value: String::from("rest"),
+                            location: Location::default(),
+                        }),
+                        location: Location::default(),
+                    }),
+                    location: Location::default(),
+                }),
+                None,
+                tuple_expr(vec![symbol_expr("first"), symbol_expr("rest")]),
+            ),
+            (
+                Pattern::Map(MapPattern {
+                    fields: vec![
+                        (String::from("name"), binding("name")),
+                        (
+                            String::from("age"),
+                            Pattern::Literal(Literal::Int(Int {
+                                value: 18,
+                                location: Location::default(),
+                            })),
+                        ),
+                    ],
+                    location: Location::default(),
+                }),
+                None,
+                symbol_expr("name"),
+            ),
+            (Pattern::Wildcard(Location::default()), None, null_expr()),
+        ];
+        eval(match_expr(value, arms), &ctx)
+    };
+    assert_eq!(describe(vector_expr(vec![])), Ok(Value::Int(0)));
+    assert_eq!(
+        describe(vector_expr(vec![int_expr(1), int_expr(2), int_expr(3)])),
+        Ok(Value::Tuple(vec![
+            Value::Int(1),
+            Value::vector(vec![Value::Int(2), Value::Int(3)])
+        ]))
+    );
+    assert_eq!(
+        describe(map_expr(vec![
+            ("name", str_expr("Ana")),
+            ("age", float_expr(18.0)),
+            ("city", str_expr("Lisbon"))
+        ])),
+        Ok(Value::String(String::from("Ana")))
+    );
+    assert_eq!(
+        describe(map_expr(vec![("name", str_expr("Bob"))])),
+        Ok(Value::Null)
+    );
+}
+#[test]
+fn test_non_exhaustive_match() {
+    let ctx = Context::new(HashScope::default());
+    let value = Expr::Literal(Literal::Atom(ast::Atom {
+        value: String::from("maybe"),
+        location: Location::new(6, 12),
+    }));
+    let arms = vec![(Pattern::Literal(atom_literal("yes")), None, bool_expr(true))];
+    assert_eq!(
+        eval(match_expr(value, arms), &ctx),
+        Err(DashlangError::new(
+            "Non-exhaustive match: no arm matches :maybe",
+            ErrorKind::NoMatch
+        )
+        .location(Location::new(6, 12)))
+    );
+}
+#[test]
+fn test_nested_destructuring() {
+    let ctx = Context::new(HashScope::default());
+    // ((a, _), [head, ...tail], {name}) = ((1, 2), [3, 4, 5], {name: "Ana", age: 31})
+    let pattern = tuple_pattern(vec![
+        tuple_pattern(vec![binding("a"), Pattern::Wildcard(Location::default())]),
+        Pattern::Vector(VectorPattern {
+            items: vec![binding("head")],
+            rest: Some(RestPattern {
+                symbol: Some(Symbol {
+                    value: String::from("tail"),
+                    location: Location::default(),
+                }),
+                location: Location::default(),
+            }),
+            location: Location::default(),
+        }),
+        Pattern::Map(MapPattern {
+            fields: vec![(String::from("name"), binding("name"))],
+            location: Location::default(),
+        }),
+    ]);
+    let value = tuple_expr(vec![
+        tuple_expr(vec![int_expr(1), int_expr(2)]),
+        vector_expr(vec![int_expr(3), int_expr(4), int_expr(5)]),
+        map_expr(vec![("name", str_expr("Ana")), ("age", int_expr(31))]),
+    ]);
+    eval(destructuring_expr(pattern, value), &ctx).unwrap();
+    assert_eq!(ctx.scope.get("a"), Value::Int(1));
+    assert_eq!(ctx.scope.get("head"), Value::Int(3));
+    assert_eq!(
+        ctx.scope.get("tail"),
+        Value::vector(vec![Value::Int(4), Value::Int(5)])
+    );
+    assert_eq!(ctx.scope.get("name"), Value::String(String::from("Ana")));
+}
+#[test]
+fn test_destructuring_mismatch() {
+    let ctx = Context::new(HashScope::default());
+    let pattern = Pattern::Tuple(TuplePattern {
+        items: vec![binding("a"), binding("b")],
+        location: Location::new(0, 6),
+    });
+    let value = tuple_expr(vec![int_expr(1), int_expr(2), int_expr(3)]);
+    assert_eq!(
+        eval(destructuring_expr(pattern, value), &ctx),
+        Err(DashlangError::new(
+            "Cannot destructure (1, 2, 3) with this pattern",
+            ErrorKind::PatternMismatch
+        )
+        .location(Location::new(0, 6)))
+    );
+    // Nothing is bound when the pattern doesn't fit
+    assert_eq!(ctx.scope.get("a"), Value::Void);
+}
+#[test]
+fn test_closure_param_patterns() {
+    let ctx = Context::new(HashScope::default());
+    // first_key = ([(key, _), ...]) { return key }
+    let closure = Expr::Literal(Literal::Closure(Closure {
+        params: vec![Pattern::Vector(VectorPattern {
+            items: vec![tuple_pattern(vec![
+                binding("key"),
+                Pattern::Wildcard(Location::default()),
+            ])],
+            rest: Some(RestPattern {
+                symbol: None,
+                location: Location::default(),
+            }),
+            location: Location::default(),
+        })],
+        body: vec![return_stmt(symbol_expr("key"))],
+        location: Location::default(),
+    }));
+    let entries = vector_expr(vec![
+        tuple_expr(vec![str_expr("a"), int_expr(1)]),
+        tuple_expr(vec![str_expr("b"), int_expr(2)]),
+    ]);
+    assert_eq!(
+        eval(call_expr(closure.clone(), vec![entries]), &ctx),
+        Ok(Value::String(String::from("a")))
+    );
+    assert_eq!(
+        eval(call_expr(closure, vec![vector_expr(vec![])]), &ctx),
+        Err(DashlangError::new(
+            "Cannot destructure [] with this pattern",
+            ErrorKind::PatternMismatch
+        )
+        .location(Location::default()))
+    );
+}
+#[test]
+fn test_if_expression() {
+    let ctx = Context::new(HashScope::default());
+    // if n > 10 { "big" } else if n > 0 { half = n / 2 half } else { -1 }
+    let sign = |n| {
+        ctx.scope.set("n", Value::Int(n));
+        let is_gt = |value| binary_expr(symbol_expr("n"), int_expr(value), BinaryOperator::Gt);
+        let else_if = Stmt::If(If {
+            cond: is_gt(0),
+            body: vec![
+                assign_stmt(
+                    "half",
+                    binary_expr(symbol_expr("n"), int_expr(2), BinaryOperator::Div),
+                ),
+                Stmt::Expr(symbol_expr("half")),
+            ],
+            else_block: Some(vec![Stmt::Expr(int_expr(-1))]),
+            location: Location::default(),
+        });
+        eval(
+            if_expr(
+                is_gt(10),
+                vec![Stmt::Expr(str_expr("big"))],
+                Some(vec![else_if]),
+            ),
+            &ctx,
+        )
+    };
+    assert_eq!(sign(30), Ok(Value::String(String::from("big"))));
+    assert_eq!(sign(8), Ok(Value::Int(4)));
+    assert_eq!(sign(-3), Ok(Value::Int(-1)));
+    // Without an else, nothing runs and the value is void
+    assert_eq!(
+        eval(
+            if_expr(bool_expr(false), vec![Stmt::Expr(int_expr(1))], None),
+            &ctx
+        ),
+        Ok(Value::Void)
+    );
+}
+#[test]
+fn test_block_expression() {
+    let ctx = Context::new(HashScope::default());
+    // { a = 2 a * 3 }
+    let block = block_expr(vec![
+        assign_stmt("a", int_expr(2)),
+        Stmt::Expr(binary_expr(
+            symbol_expr("a"),
+            int_expr(3),
+            BinaryOperator::Mul,
+        )),
+    ]);
+    assert_eq!(eval(block, &ctx), Ok(Value::Int(6)));
+    assert_eq!(ctx.scope.get("a"), Value::Int(2));
+    // A block that doesn't end with an expression is void
+    assert_eq!(eval(block_expr(vec![]), &ctx), Ok(Value::Void));
+    assert_eq!(
+        eval(
+            block_expr(vec![if_stmt(
+                bool_expr(true),
+                vec![Stmt::Expr(int_expr(1))]
+            )]),
+            &ctx
+        ),
+        Ok(Value::Int(1))
+    );
+}
+#[test]
+fn test_jump_out_of_value_block() {
+    let ctx = Context::new(HashScope::default());
+    let block = block_expr(vec![Stmt::Break(Break {
+        label: None,
+        location: Location::new(2, 7),
+    })]);
+    assert_eq!(
+        eval(block, &ctx),
+        Err(DashlangError::new(
+            "'break' can't jump out of a block used as a value",
+            ErrorKind::Unknown
+        )
+        .location(Location::new(2, 7)))
     );
 }
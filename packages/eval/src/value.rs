@@ -0,0 +1,198 @@
+use std::{any::Any, cell::RefCell, collections::HashMap, fmt::Debug, rc::Rc};
+
+use ast::{Pattern, Program};
+use num_bigint::BigInt;
+
+/// The scope type is generic over the evaluator, so closures keep it as an opaque handle
+#[derive(Clone)]
+pub struct Environment(pub Rc<dyn Any>);
+impl Debug for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Environment")
+    }
+}
+impl PartialEq for Environment {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Closure {
+    pub params: Vec<Pattern>,
+    pub body: Program,
+    pub env: Environment,
+}
+
+pub type Vector = Rc<RefCell<Vec<Value>>>;
+pub type Map = Rc<RefCell<HashMap<String, Value>>>;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Range {
+    pub start: i64,
+    pub step: i64,
+    pub len: u64,
+}
+impl Range {
+    /// Gives `None` when the range has more elements than a `u64` can count
+    pub fn new(start: i64, end: i64, step: i64, inclusive: bool) -> Option<Self> {
+        let step_size = i128::from(step).abs();
+        let mut span = i128::from(end) - i128::from(start);
+        if step < 0 {
+            span = -span;
+        }
+        if inclusive {
+            span += 1;
+        }
+        let len = if span > 0 {
+            (span + step_size - 1) / step_size
+        } else {
+            0
+        };
+        Some(Self {
+            start,
+            step,
+            len: u64::try_from(len).ok()?,
+        })
+    }
+    fn nth(&self, index: u64) -> i128 {
+        i128::from(self.start) + i128::from(index) * i128::from(self.step)
+    }
+    pub fn get(&self, index: u64) -> Option<i64> {
+        // Every element is between `start` and `end`, so it fits
+        (index < self.len).then(|| self.nth(index) as i64)
+    }
+    pub fn last(&self) -> Option<i64> {
+        self.len.checked_sub(1).and_then(|index| self.get(index))
+    }
+    /// First integer after the range, unless it doesn't fit in an `i64`
+    pub fn end(&self) -> Option<i64> {
+        i64::try_from(self.nth(self.len)).ok()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = i64> {
+        let range = *self;
+        (0..range.len).map(move |index| range.nth(index) as i64)
+    }
+    /// The indexes must all be in bounds. Gives `None` when the new step doesn't fit in an `i64`
+    pub fn select(&self, indexes: &Range) -> Option<Self> {
+        let step = if indexes.len > 1 {
+            self.step.checked_mul(indexes.step)?
+        } else {
+            self.step
+        };
+        Some(Self {
+            start: indexes
+                .get(0)
+                .map_or(self.start, |index| self.nth(index as u64) as i64),
+            step,
+            len: indexes.len,
+        })
+    }
+    pub fn same_elements(&self, other: &Range) -> bool {
+        self.len == other.len
+            && (self.len == 0 || self.start == other.start)
+            && (self.len <= 1 || self.step == other.step)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Closure(Closure),
+    Int(i64),
+    BigInt(BigInt),
+    Float(f64),
+    String(String),
+    Bool(bool),
+    Vector(Vector),
+    Null,
+    Void,
+    Tuple(Vec<Value>),
+    Map(Map),
+    Atom(String),
+    Range(Range),
+}
+
+impl Value {
+    pub fn vector(items: Vec<Value>) -> Self {
+        Value::Vector(Rc::new(RefCell::new(items)))
+    }
+    pub fn map(attributes: HashMap<String, Value>) -> Self {
+        Value::Map(Rc::new(RefCell::new(attributes)))
+    }
+    pub fn deep_copy(&self) -> Self {
+        self.copy_with(&mut HashMap::new())
+    }
+    /// Reuses the copies already made, so collections that contain themselves are copied once
+    fn copy_with(&self, copies: &mut HashMap<*const (), Value>) -> Self {
+        match self {
+            Value::Vector(items) => {
+                let key = Rc::as_ptr(items).cast();
+                if let Some(copy) = copies.get(&key) {
+                    return copy.clone();
+                }
+                let copy: Vector = Rc::default();
+                copies.insert(key, Value::Vector(copy.clone()));
+                let copied = items
+                    .borrow()
+                    .iter()
+                    .map(|item| item.copy_with(copies))
+                    .collect();
+                *copy.borrow_mut() = copied;
+                Value::Vector(copy)
+            }
+            Value::Tuple(items) => {
+                Value::Tuple(items.iter().map(|item| item.copy_with(copies)).collect())
+            }
+            Value::Map(attributes) => {
+                let key = Rc::as_ptr(attributes).cast();
+                if let Some(copy) = copies.get(&key) {
+                    return copy.clone();
+                }
+                let copy: Map = Rc::default();
+                copies.insert(key, Value::Map(copy.clone()));
+                let copied = attributes
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.copy_with(copies)))
+                    .collect();
+                *copy.borrow_mut() = copied;
+                Value::Map(copy)
+            }
+            value => value.clone(),
+        }
+    }
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            Value::Closure(_) => "closure",
+            Value::Int(_) | Value::BigInt(_) => "integer",
+            Value::Float(_) => "float",
+            Value::String(_) => "string",
+            Value::Bool(_) => "boolean",
+            Value::Vector(_) => "vector",
+            Value::Null => "null",
+            Value::Void => "void",
+            Value::Tuple(_) => "tuple",
+            Value::Map(_) => "map",
+            Value::Atom(_) => "atom",
+            Value::Range(_) => "range",
+        }
+    }
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Closure(_) => true,
+            Value::Int(num) => *num != 0,
+            // Big integers are never zero, since zero fits in a regular integer
+            Value::BigInt(_) => true,
+            Value::Float(num) => *num != 0.0,
+            Value::String(string) => !string.is_empty(),
+            Value::Vector(items) => !items.borrow().is_empty(),
+            Value::Bool(value) => *value,
+            Value::Null => false,
+            Value::Void => false,
+            Value::Tuple(_) => false,
+            Value::Map(map) => !map.borrow().is_empty(),
+            Value::Atom(_) => true,
+            Value::Range(range) => range.len > 0,
+        }
+    }
+}
@@ -1,47 +1,66 @@
 use errors::DashlangResult;
-use pest::Parser;
+use pest::iterators::Pair;
 
-use crate::{expression::parse_expression, utils::get_pair_location, DashlangParser, Rule};
-use ast::{AssignmentExpr, Location};
+use crate::{
+    expression::parse_expression,
+    utils::{get_pair_location, next_pair},
+    Rule,
+};
+use ast::{AssignmentExpr, Expr};
 
-pub fn parse_assignment_expression(
-    input: &str,
-    base_location: usize,
-) -> DashlangResult<AssignmentExpr> {
-    let ast = DashlangParser::parse(Rule::assignment_expression, input)
-        .expect("Could not parse assignment expression")
-        .next()
-        .expect("Could not parse assignment expression");
-    let (start, end) = get_pair_location(&ast);
+use super::{parse_symbol, postfix_expression::parse_accessor};
+
+pub struct AssignmentTarget {
+    pub symbol: String,
+    pub path: Vec<Expr>,
+}
+
+pub fn parse_assignment_target(ast: Pair<Rule>) -> DashlangResult<AssignmentTarget> {
+    let location = get_pair_location(&ast);
     let mut ast_inner = ast.into_inner();
-    let ast_symbol = ast_inner
-        .next()
-        .expect("Could not get assignment expression symbol");
-    let ast_value = ast_inner
-        .next()
-        .expect("Could not get assignment expression value");
-    let (start_value, _) = get_pair_location(&ast_value);
+    let symbol = parse_symbol(next_pair(
+        &mut ast_inner,
+        location,
+        "assignment target symbol",
+    )?);
+    let path = ast_inner
+        .map(parse_accessor)
+        .collect::<DashlangResult<_>>()?;
+    Ok(AssignmentTarget {
+        symbol: symbol.value,
+        path,
+    })
+}
+
+pub fn parse_assignment_expression(ast: Pair<Rule>) -> DashlangResult<AssignmentExpr> {
+    let location = get_pair_location(&ast);
+    let mut ast_inner = ast.into_inner();
+    let ast_target = next_pair(&mut ast_inner, location, "assignment expression target")?;
+    let target = parse_assignment_target(ast_target)?;
+    let ast_value = next_pair(&mut ast_inner, location, "assignment expression value")?;
     Ok(AssignmentExpr {
-        symbol: ast_symbol.as_str().to_owned(),
-        value: Box::new(parse_expression(
-            ast_value.as_str(),
-            start_value + base_location,
-        )?),
-        location: Location::new(start + base_location, end + base_location),
+        symbol: target.symbol,
+        path: target.path,
+        operator: None,
+        value: Box::new(parse_expression(ast_value)?),
+        location,
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use ast::{BinaryExpr, BinaryOperator, Expr, Int, Literal};
+    use ast::{BinaryExpr, BinaryOperator, Int, Literal, Location, Str, Symbol};
 
     use super::*;
+    use crate::utils::parse_rule;
     #[test]
     fn test_parse_value_assignment() {
         assert_eq!(
-            parse_assignment_expression("age = 5", 0),
+            parse_assignment_expression(parse_rule(Rule::assignment_expression, "age = 5")),
             Ok(AssignmentExpr {
                 symbol: String::from("age"),
+                path: vec![],
+                operator: None,
                 value: Box::new(Expr::Literal(Literal::Int(Int {
                     value: 5,
                     location: Location::new(6, 7)
@@ -53,9 +72,11 @@ mod tests {
     #[test]
     fn test_parse_expr_assignment() {
         assert_eq!(
-            parse_assignment_expression("age = 5 + 1", 0),
+            parse_assignment_expression(parse_rule(Rule::assignment_expression, "age = 5 + 1")),
             Ok(AssignmentExpr {
                 symbol: String::from("age"),
+                path: vec![],
+                operator: None,
                 value: Box::new(Expr::BinaryExpr(Box::new(BinaryExpr {
                     left: Expr::Literal(Literal::Int(Int {
                         value: 5,
@@ -72,4 +93,51 @@ mod tests {
             })
         );
     }
+    #[test]
+    fn test_parse_path_assignment() {
+        assert_eq!(
+            parse_assignment_expression(parse_rule(
+                Rule::assignment_expression,
+                r#"scores["ana"][i] = 10"#
+            )),
+            Ok(AssignmentExpr {
+                symbol: String::from("scores"),
+                path: vec![
+                    Expr::Literal(Literal::String(Str {
+                        value: String::from("ana"),
+                        location: Location::new(7, 12)
+                    })),
+                    Expr::Symbol(Symbol {
+                        value: String::from("i"),
+                        location: Location::new(14, 15)
+                    }),
+                ],
+                operator: None,
+                value: Box::new(Expr::Literal(Literal::Int(Int {
+                    value: 10,
+                    location: Location::new(19, 21)
+                }))),
+                location: Location::new(0, 21),
+            })
+        );
+        assert_eq!(
+            parse_assignment_expression(parse_rule(
+                Rule::assignment_expression,
+                "player.score = 0"
+            )),
+            Ok(AssignmentExpr {
+                symbol: String::from("player"),
+                path: vec![Expr::Literal(Literal::String(Str {
+                    value: String::from("score"),
+                    location: Location::new(7, 12)
+                }))],
+                operator: None,
+                value: Box::new(Expr::Literal(Literal::Int(Int {
+                    value: 0,
+                    location: Location::new(15, 16)
+                }))),
+                location: Location::new(0, 16),
+            })
+        );
+    }
 }
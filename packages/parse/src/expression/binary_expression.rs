@@ -1,75 +1,60 @@
+use std::sync::LazyLock;
+
 use super::{
-    binary_operator::parse_binary_operator, call_expression::parse_call_expression,
-    parse_expression, unary_expression::parse_unary_expression,
+    binary_operator::parse_binary_operator, block_expression::parse_block_expression,
+    match_expression::parse_match_expression, postfix_expression::parse_postfix_expression,
+    unary_expression::parse_unary_expression,
 };
 use crate::{
-    expression::parse_sub_expression, literal::parse_literal, utils::get_pair_location,
-    DashlangParser, Rule,
+    statement::if_stmt::parse_if_stmt,
+    utils::{enter_expression, get_pair_location, unexpected_pair},
+    Rule,
 };
-use ast::{BinaryExpr, Expr, Location, Symbol};
-use errors::{DashlangError, DashlangResult, ErrorKind};
+use ast::{BinaryExpr, Expr, Location};
+use errors::DashlangResult;
 use pest::{
+    iterators::Pair,
     pratt_parser::{Assoc, Op, PrattParser},
-    Parser,
 };
 
-pub fn parse_binary_expression(input: &str, base_location: usize) -> DashlangResult<BinaryExpr> {
-    let pratt = PrattParser::new()
-        .op(Op::infix(Rule::and, Assoc::Left)
-            | Op::infix(Rule::eq, Assoc::Left)
-            | Op::infix(Rule::or, Assoc::Left))
+/// Levels go from loosest to tightest binding
+static PRATT: LazyLock<PrattParser<Rule>> = LazyLock::new(|| {
+    PrattParser::new()
+        .op(Op::infix(Rule::or, Assoc::Left))
+        .op(Op::infix(Rule::and, Assoc::Left))
+        .op(Op::infix(Rule::eq, Assoc::Left) | Op::infix(Rule::ne, Assoc::Left))
         .op(Op::infix(Rule::lt, Assoc::Left)
             | Op::infix(Rule::le, Assoc::Left)
             | Op::infix(Rule::gt, Assoc::Left)
             | Op::infix(Rule::ge, Assoc::Left))
+        .op(Op::infix(Rule::bitwise_or, Assoc::Left))
+        .op(Op::infix(Rule::bitwise_xor, Assoc::Left))
+        .op(Op::infix(Rule::bitwise_and, Assoc::Left))
+        .op(Op::infix(Rule::bitwise_left_shift, Assoc::Left)
+            | Op::infix(Rule::bitwise_right_shift, Assoc::Left))
         .op(Op::infix(Rule::add, Assoc::Left) | Op::infix(Rule::sub, Assoc::Left))
-        .op(Op::infix(Rule::mul, Assoc::Left) | Op::infix(Rule::div, Assoc::Left))
-        .op(Op::infix(Rule::bitwise_right_shift, Assoc::Left)
-            | Op::infix(Rule::bitwise_left_shift, Assoc::Left)
-            | Op::infix(Rule::bitwise_and, Assoc::Left)
-            | Op::infix(Rule::bitwise_or, Assoc::Left)
-            | Op::infix(Rule::bitwise_xor, Assoc::Left));
+        .op(Op::infix(Rule::mul, Assoc::Left)
+            | Op::infix(Rule::div, Assoc::Left)
+            | Op::infix(Rule::int_div, Assoc::Left)
+            | Op::infix(Rule::modulo, Assoc::Left))
+        .op(Op::infix(Rule::pow, Assoc::Right))
+});
 
-    let pairs = DashlangParser::parse(Rule::binary_expression, input)
-        .unwrap()
-        .next()
-        .unwrap()
-        .into_inner();
-    let parsed = pratt
-        .map_primary(|primary| {
-            let primary_start = primary.as_span().start();
-            match primary.as_rule() {
-                Rule::literal => Ok(Expr::Literal(parse_literal(
-                    primary.as_str(),
-                    base_location + primary_start,
-                )?)),
-                Rule::symbol => Ok({
-                    Expr::Symbol(Symbol {
-                        value: primary.as_str().to_owned(),
-                        location: {
-                            let (start, end) = get_pair_location(&primary);
-                            (start + base_location, end + base_location).into()
-                        },
-                    })
-                }),
-                Rule::sub_expression => Ok(Expr::SubExpr(parse_sub_expression(
-                    primary.as_str(),
-                    primary_start + base_location,
-                )?)),
-                Rule::expression => Ok(parse_expression(
-                    primary.as_str(),
-                    primary_start + base_location,
-                )?),
-                Rule::unary_expression => Ok(Expr::UnaryExpr(Box::new(parse_unary_expression(
-                    primary.as_str(),
-                    primary_start + base_location,
-                )?))),
-                Rule::call_expression => Ok(Expr::Call(parse_call_expression(
-                    primary.as_str(),
-                    primary_start + base_location,
-                )?)),
-                _ => unreachable!("{:#?}", primary.as_rule()),
+/// Precedence is listed in the README, and all operators but `**` are left associative
+pub fn parse_binary_expression(ast: Pair<Rule>) -> DashlangResult<Expr> {
+    // Operands and operators alternate, and each operator adds a level to the AST
+    let operators = ast.clone().into_inner().count() / 2;
+    let _depth = enter_expression(1 + operators, get_pair_location(&ast))?;
+    PRATT
+        .map_primary(|primary| match primary.as_rule() {
+            Rule::postfix_expression => parse_postfix_expression(primary),
+            Rule::unary_expression => {
+                Ok(Expr::UnaryExpr(Box::new(parse_unary_expression(primary)?)))
             }
+            Rule::match_expression => Ok(Expr::Match(Box::new(parse_match_expression(primary)?))),
+            Rule::if_expression => Ok(Expr::If(Box::new(parse_if_stmt(primary)?))),
+            Rule::block_expression => Ok(Expr::Block(parse_block_expression(primary)?)),
+            _ => Err(unexpected_pair(&primary)),
         })
         .map_infix(|lhs, op, rhs| {
             let lhs = lhs?;
@@ -82,29 +67,21 @@ pub fn parse_binary_expression(input: &str, base_location: usize) -> DashlangRes
                 operator: parse_binary_operator(op.as_str())?,
             })))
         })
-        .parse(pairs)?;
-    if let Expr::BinaryExpr(bin_expr) = parsed {
-        Ok(*bin_expr)
-    } else {
-        Err(DashlangError {
-            location: Some(Location::default()),
-            message: "Expected binary expression".to_owned(),
-            kind: ErrorKind::Unknown,
-        })
-    }
+        .parse(ast.into_inner())
 }
 
 #[cfg(test)]
 mod tests {
-    use ast::{BinaryOperator, Boolean, Int, Literal, SubExpr};
+    use ast::{Access, BinaryOperator, Boolean, Int, Literal, SubExpr, Symbol};
 
     use super::*;
+    use crate::utils::parse_rule;
 
     #[test]
     fn test_parse_binary_op() {
         assert_eq!(
-            parse_binary_expression("1 * 2", 0),
-            Ok(BinaryExpr {
+            parse_binary_expression(parse_rule(Rule::binary_expression, "1 * 2")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::Literal(Literal::Int(Int {
                     value: 1,
                     location: Location::new(0, 1)
@@ -115,11 +92,11 @@ mod tests {
                 })),
                 operator: BinaryOperator::Mul,
                 location: Location::new(0, 5),
-            })
+            })))
         );
         assert_eq!(
-            parse_binary_expression("1 + 2", 0),
-            Ok(BinaryExpr {
+            parse_binary_expression(parse_rule(Rule::binary_expression, "1 + 2")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::Literal(Literal::Int(Int {
                     value: 1,
                     location: Location::new(0, 1)
@@ -130,11 +107,11 @@ mod tests {
                 })),
                 operator: BinaryOperator::Add,
                 location: Location::new(0, 5),
-            })
+            })))
         );
         assert_eq!(
-            parse_binary_expression("1 + 2 * 2", 0),
-            Ok(BinaryExpr {
+            parse_binary_expression(parse_rule(Rule::binary_expression, "1 + 2 * 2")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::Literal(Literal::Int(Int {
                     value: 1,
                     location: Location::new(0, 1)
@@ -153,14 +130,14 @@ mod tests {
                 })),
                 operator: BinaryOperator::Add,
                 location: Location::new(0, 9),
-            })
+            })))
         );
     }
     #[test]
     fn test_parse_binary_expr() {
         assert_eq!(
-            parse_binary_expression("1 + 2", 0),
-            Ok(BinaryExpr {
+            parse_binary_expression(parse_rule(Rule::binary_expression, "1 + 2")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::Literal(Literal::Int(Int {
                     value: 1,
                     location: Location::new(0, 1)
@@ -171,11 +148,11 @@ mod tests {
                 })),
                 operator: BinaryOperator::Add,
                 location: Location::new(0, 5),
-            })
+            })))
         );
         assert_eq!(
-            parse_binary_expression("2 > 1", 0),
-            Ok(BinaryExpr {
+            parse_binary_expression(parse_rule(Rule::binary_expression, "2 > 1")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::Literal(Literal::Int(Int {
                     value: 2,
                     location: Location::new(0, 1)
@@ -186,11 +163,11 @@ mod tests {
                 })),
                 operator: BinaryOperator::Gt,
                 location: Location::new(0, 5),
-            })
+            })))
         );
         assert_eq!(
-            parse_binary_expression("2 == 2", 0),
-            Ok(BinaryExpr {
+            parse_binary_expression(parse_rule(Rule::binary_expression, "2 == 2")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::Literal(Literal::Int(Int {
                     value: 2,
                     location: Location::new(0, 1)
@@ -201,11 +178,11 @@ mod tests {
                 })),
                 operator: BinaryOperator::Eq,
                 location: Location::new(0, 6),
-            })
+            })))
         );
         assert_eq!(
-            parse_binary_expression("true || false", 0),
-            Ok(BinaryExpr {
+            parse_binary_expression(parse_rule(Rule::binary_expression, "true || false")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::Literal(Literal::Bool(Boolean {
                     value: true,
                     location: Location::new(0, 4)
@@ -216,11 +193,11 @@ mod tests {
                 })),
                 operator: BinaryOperator::Or,
                 location: Location::new(0, 13),
-            })
+            })))
         );
         assert_eq!(
-            parse_binary_expression("true && false", 0),
-            Ok(BinaryExpr {
+            parse_binary_expression(parse_rule(Rule::binary_expression, "true && false")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::Literal(Literal::Bool(Boolean {
                     value: true,
                     location: Location::new(0, 4)
@@ -231,14 +208,46 @@ mod tests {
                 })),
                 operator: BinaryOperator::And,
                 location: Location::new(0, 13),
-            })
+            })))
+        );
+    }
+    #[test]
+    fn test_parse_access_operands() {
+        assert_eq!(
+            parse_binary_expression(parse_rule(Rule::binary_expression, "v[0] + t.1")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
+                left: Expr::Access(Box::new(Access {
+                    target: Expr::Symbol(Symbol {
+                        value: String::from("v"),
+                        location: Location::new(0, 1)
+                    }),
+                    key: Expr::Literal(Literal::Int(Int {
+                        value: 0,
+                        location: Location::new(2, 3)
+                    })),
+                    location: Location::new(0, 4),
+                })),
+                right: Expr::Access(Box::new(Access {
+                    target: Expr::Symbol(Symbol {
+                        value: String::from("t"),
+                        location: Location::new(7, 8)
+                    }),
+                    key: Expr::Literal(Literal::Int(Int {
+                        value: 1,
+                        location: Location::new(9, 10)
+                    })),
+                    location: Location::new(7, 10),
+                })),
+                operator: BinaryOperator::Add,
+                location: Location::new(0, 10),
+            })))
         );
     }
     #[test]
     fn test_parse_sub_expressions() {
         assert_eq!(
-            parse_binary_expression("1 + (2 + 1)", 0),
-            Ok(BinaryExpr {
+            parse_binary_expression(parse_rule(Rule::binary_expression, "1 + (2 + 1)")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::Literal(Literal::Int(Int {
                     value: 1,
                     location: Location::new(0, 1)
@@ -260,11 +269,11 @@ mod tests {
                 }),
                 operator: BinaryOperator::Add,
                 location: Location::new(0, 11),
-            }),
+            }))),
         );
         assert_eq!(
-            parse_binary_expression("(1 + 2) + 1", 0),
-            Ok(BinaryExpr {
+            parse_binary_expression(parse_rule(Rule::binary_expression, "(1 + 2) + 1")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::SubExpr(SubExpr {
                     value: Box::new(Expr::BinaryExpr(Box::new(BinaryExpr {
                         left: Expr::Literal(Literal::Int(Int {
@@ -286,11 +295,11 @@ mod tests {
                 })),
                 operator: BinaryOperator::Add,
                 location: Location::new(0, 11),
-            })
+            })))
         );
         assert_eq!(
-            parse_binary_expression("1 + n", 0),
-            Ok(BinaryExpr {
+            parse_binary_expression(parse_rule(Rule::binary_expression, "1 + n")),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::Literal(Literal::Int(Int {
                     value: 1,
                     location: Location::new(0, 1)
@@ -301,7 +310,122 @@ mod tests {
                     location: Location::new(4, 5)
                 }),
                 location: Location::new(0, 5),
-            })
+            })))
+        );
+    }
+
+    fn grouping(input: &str) -> String {
+        fn group(expr: &Expr, input: &str) -> String {
+            match expr {
+                Expr::BinaryExpr(binary) => {
+                    let left_end = binary.left.get_location().end;
+                    let right_start = binary.right.get_location().start;
+                    format!(
+                        "({} {} {})",
+                        group(&binary.left, input),
+                        input[left_end..right_start].trim(),
+                        group(&binary.right, input)
+                    )
+                }
+                Expr::UnaryExpr(unary) => {
+                    let operator_end = unary.operand.get_location().start;
+                    format!(
+                        "({}{})",
+                        &input[unary.location.start..operator_end],
+                        group(&unary.operand, input)
+                    )
+                }
+                other => {
+                    let location = other.get_location();
+                    input[location.start..location.end].to_owned()
+                }
+            }
+        }
+        let expr = parse_binary_expression(parse_rule(Rule::binary_expression, input)).unwrap();
+        group(&expr, input)
+    }
+    #[test]
+    fn test_precedence_or_and() {
+        assert_eq!(grouping("a || b && c"), "(a || (b && c))");
+        assert_eq!(grouping("a && b || c"), "((a && b) || c)");
+    }
+    #[test]
+    fn test_precedence_and_equality() {
+        assert_eq!(grouping("a == 1 && b == 2"), "((a == 1) && (b == 2))");
+        assert_eq!(grouping("a == 1 || b == 2"), "((a == 1) || (b == 2))");
+    }
+    #[test]
+    fn test_precedence_equality_comparison() {
+        assert_eq!(grouping("a < b == c >= d"), "((a < b) == (c >= d))");
+        assert_eq!(grouping("a == b <= c"), "(a == (b <= c))");
+    }
+    #[test]
+    fn test_precedence_comparison_bitwise_or() {
+        assert_eq!(grouping("a | b < c | d"), "((a | b) < (c | d))");
+        assert_eq!(grouping("a > b | c"), "(a > (b | c))");
+    }
+    #[test]
+    fn test_precedence_bitwise_or_xor() {
+        assert_eq!(grouping("a | b ^ c"), "(a | (b ^ c))");
+        assert_eq!(grouping("a ^ b | c"), "((a ^ b) | c)");
+    }
+    #[test]
+    fn test_precedence_xor_bitwise_and() {
+        assert_eq!(grouping("a ^ b & c"), "(a ^ (b & c))");
+        assert_eq!(grouping("a & b ^ c"), "((a & b) ^ c)");
+    }
+    #[test]
+    fn test_precedence_bitwise_and_shift() {
+        assert_eq!(grouping("a & b << c"), "(a & (b << c))");
+        assert_eq!(grouping("a >> b & c"), "((a >> b) & c)");
+    }
+    #[test]
+    fn test_precedence_shift_additive() {
+        assert_eq!(grouping("a << b + c"), "(a << (b + c))");
+        assert_eq!(grouping("a - b >> c"), "((a - b) >> c)");
+    }
+    #[test]
+    fn test_precedence_additive_multiplicative() {
+        assert_eq!(grouping("a + b * c"), "(a + (b * c))");
+        assert_eq!(grouping("a / b - c"), "((a / b) - c)");
+    }
+    #[test]
+    fn test_precedence_multiplicative_unary() {
+        assert_eq!(grouping("~a * b"), "((~a) * b)");
+        assert_eq!(grouping("a / ~b"), "(a / (~b))");
+        assert_eq!(grouping("!a && !!b"), "((!a) && (!(!b)))");
+    }
+    #[test]
+    fn test_precedence_left_associative() {
+        assert_eq!(grouping("a - b - c"), "((a - b) - c)");
+        assert_eq!(grouping("a << b >> c"), "((a << b) >> c)");
+        assert_eq!(grouping("a == b == c"), "((a == b) == c)");
+    }
+    #[test]
+    fn test_precedence_c_like_mixes() {
+        assert_eq!(grouping("x & 1 == 0"), "((x & 1) == 0)");
+        assert_eq!(grouping("(a || b) && c"), "((a || b) && c)");
+    }
+    #[test]
+    fn test_precedence_new_operators() {
+        assert_eq!(grouping("a != b < c"), "(a != (b < c))");
+        assert_eq!(grouping("a == b != c"), "((a == b) != c)");
+        assert_eq!(grouping("a + b % c"), "(a + (b % c))");
+        assert_eq!(grouping("a ~/ b * c"), "((a ~/ b) * c)");
+        assert_eq!(grouping("a * b ** c"), "(a * (b ** c))");
+        assert_eq!(grouping("a ** b ** c"), "(a ** (b ** c))");
+        assert_eq!(grouping("-a ** b"), "((-a) ** b)");
+        assert_eq!(grouping("a - -b"), "(a - (-b))");
+        assert_eq!(grouping("n % 2 != 0"), "((n % 2) != 0)");
+    }
+    #[test]
+    fn test_negative_literal_is_not_unary() {
+        assert_eq!(
+            parse_binary_expression(parse_rule(Rule::binary_expression, "-5")),
+            Ok(Expr::Literal(Literal::Int(Int {
+                value: -5,
+                location: Location::new(0, 2)
+            })))
         );
     }
 }
@@ -7,11 +7,15 @@ pub fn parse_binary_operator(input: &str) -> DashlangResult<BinaryOperator> {
         "-" => Ok(BinaryOperator::Sub),
         "*" => Ok(BinaryOperator::Mul),
         "/" => Ok(BinaryOperator::Div),
+        "~/" => Ok(BinaryOperator::IntDiv),
+        "%" => Ok(BinaryOperator::Mod),
+        "**" => Ok(BinaryOperator::Pow),
         ">" => Ok(BinaryOperator::Gt),
         ">=" => Ok(BinaryOperator::Ge),
         "<" => Ok(BinaryOperator::Lt),
         "<=" => Ok(BinaryOperator::Le),
         "==" => Ok(BinaryOperator::Eq),
+        "!=" => Ok(BinaryOperator::Ne),
         "&&" => Ok(BinaryOperator::And),
         "||" => Ok(BinaryOperator::Or),
         "&" => Ok(BinaryOperator::BitwiseAnd),
@@ -0,0 +1,47 @@
+use ast::Block;
+use errors::DashlangResult;
+use pest::iterators::Pair;
+
+use crate::{body::parse_body, parser::Rule, utils::get_pair_location};
+
+pub fn parse_block_expression(ast: Pair<Rule>) -> DashlangResult<Block> {
+    let location = get_pair_location(&ast);
+    Ok(Block {
+        body: parse_body(ast)?,
+        location,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ast::{AssignmentExpr, Expr, Int, Literal, Location, Stmt, Symbol};
+
+    use super::*;
+    use crate::utils::parse_rule;
+
+    #[test]
+    fn test_block_expression() {
+        assert_eq!(
+            parse_block_expression(parse_rule(Rule::block_expression, "{ a = 1 a }")),
+            Ok(Block {
+                body: vec![
+                    Stmt::Expr(Expr::Assignment(AssignmentExpr {
+                        symbol: "a".to_owned(),
+                        path: vec![],
+                        operator: None,
+                        value: Box::new(Expr::Literal(Literal::Int(Int {
+                            value: 1,
+                            location: Location::new(6, 7)
+                        }))),
+                        location: Location::new(2, 7),
+                    })),
+                    Stmt::Expr(Expr::Symbol(Symbol {
+                        value: "a".to_owned(),
+                        location: Location::new(8, 9)
+                    })),
+                ],
+                location: Location::new(0, 11),
+            })
+        );
+    }
+}
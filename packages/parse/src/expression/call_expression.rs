@@ -1,55 +1,35 @@
-use ast::{Call, Expr, Location};
+use ast::{Call, Expr};
 use errors::DashlangResult;
-use pest::Parser;
+use pest::iterators::Pair;
 
-use crate::{
-    parser::{DashlangParser, Rule},
-    utils::get_pair_location,
-};
+use crate::{parser::Rule, utils::unexpected_pair};
 
-use super::parse_expression;
+use super::postfix_expression::parse_postfix_expression;
 
-pub fn parse_call_expression(input: &str, base_location: usize) -> DashlangResult<Call> {
-    let ast = DashlangParser::parse(Rule::call_expression, input)
-        .expect("Could not parse call expression")
-        .next()
-        .expect("Could not parse call expression");
-    let (start, end) = get_pair_location(&ast);
-    let mut ast_inner = ast.into_inner();
-    let symbol = ast_inner
-        .next()
-        .expect("Could not get call symbol")
-        .as_str()
-        .to_owned();
-    let args: DashlangResult<Vec<Expr>> = ast_inner
-        .map(|element| {
-            let inner_arg = element
-                .into_inner()
-                .next()
-                .expect("Could not get call arg content");
-            let (arg_start, _) = get_pair_location(&inner_arg);
-            parse_expression(inner_arg.as_str(), arg_start + base_location)
-        })
-        .collect();
-    Ok(Call {
-        symbol,
-        args: args?,
-        location: Location::new(start + base_location, end + base_location),
-    })
+pub fn parse_call_expression(ast: Pair<Rule>) -> DashlangResult<Call> {
+    let error = unexpected_pair(&ast);
+    match parse_postfix_expression(ast)? {
+        Expr::Call(call) => Ok(call),
+        _ => Err(error),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use ast::{Expr, Int, Literal, Symbol};
+    use ast::{Int, Literal, Location, Symbol};
 
     use super::*;
+    use crate::utils::parse_rule;
 
     #[test]
     fn test_parse_call() {
         assert_eq!(
-            parse_call_expression("println()", 0),
+            parse_call_expression(parse_rule(Rule::call_expression, "println()")),
             Ok(Call {
-                symbol: String::from("println"),
+                callee: Box::new(Expr::Symbol(Symbol {
+                    value: String::from("println"),
+                    location: Location::new(0, 7)
+                })),
                 args: vec![],
                 location: Location::new(0, 9)
             })
@@ -58,9 +38,12 @@ mod tests {
     #[test]
     fn test_parse_call_with_args() {
         assert_eq!(
-            parse_call_expression("println(18)", 0),
+            parse_call_expression(parse_rule(Rule::call_expression, "println(18)")),
             Ok(Call {
-                symbol: String::from("println"),
+                callee: Box::new(Expr::Symbol(Symbol {
+                    value: String::from("println"),
+                    location: Location::new(0, 7)
+                })),
                 args: vec![Expr::Literal(Literal::Int(Int {
                     value: 18,
                     location: Location::new(8, 10)
@@ -69,9 +52,12 @@ mod tests {
             })
         );
         assert_eq!(
-            parse_call_expression("println(name)", 0),
+            parse_call_expression(parse_rule(Rule::call_expression, "println(name)")),
             Ok(Call {
-                symbol: String::from("println"),
+                callee: Box::new(Expr::Symbol(Symbol {
+                    value: String::from("println"),
+                    location: Location::new(0, 7)
+                })),
                 args: vec![Expr::Symbol(Symbol {
                     value: String::from("name"),
                     location: Location::new(8, 12)
@@ -80,11 +66,17 @@ mod tests {
             })
         );
         assert_eq!(
-            parse_call_expression("println(getName())", 0),
+            parse_call_expression(parse_rule(Rule::call_expression, "println(getName())")),
             Ok(Call {
-                symbol: String::from("println"),
+                callee: Box::new(Expr::Symbol(Symbol {
+                    value: String::from("println"),
+                    location: Location::new(0, 7)
+                })),
                 args: vec![Expr::Call(Call {
-                    symbol: String::from("getName"),
+                    callee: Box::new(Expr::Symbol(Symbol {
+                        value: String::from("getName"),
+                        location: Location::new(8, 15)
+                    })),
                     args: vec![],
                     location: Location::new(8, 17)
                 })],
@@ -92,11 +84,17 @@ mod tests {
             })
         );
         assert_eq!(
-            parse_call_expression("println(getName(id))", 0),
+            parse_call_expression(parse_rule(Rule::call_expression, "println(getName(id))")),
             Ok(Call {
-                symbol: String::from("println"),
+                callee: Box::new(Expr::Symbol(Symbol {
+                    value: String::from("println"),
+                    location: Location::new(0, 7)
+                })),
                 args: vec![Expr::Call(Call {
-                    symbol: String::from("getName"),
+                    callee: Box::new(Expr::Symbol(Symbol {
+                        value: String::from("getName"),
+                        location: Location::new(8, 15)
+                    })),
                     args: vec![Expr::Symbol(Symbol {
                         value: String::from("id"),
                         location: Location::new(16, 18)
@@ -1,97 +1,121 @@
-use ast::{AssignmentExpr, BinaryExpr, Expr, Location, Symbol};
+use ast::AssignmentExpr;
 use errors::DashlangResult;
-use pest::Parser;
+use pest::iterators::Pair;
 
 use crate::{
-    parser::{DashlangParser, Rule},
-    utils::get_pair_location,
+    parser::Rule,
+    utils::{get_pair_location, next_pair},
 };
 
-use super::{binary_operator::parse_binary_operator, parse_expression};
+use super::{
+    assignment_expression::parse_assignment_target, binary_operator::parse_binary_operator,
+    parse_expression,
+};
 
-pub fn parse_compound_assign_expr(
-    input: &str,
-    base_location: usize,
-) -> DashlangResult<AssignmentExpr> {
-    let ast = DashlangParser::parse(Rule::compound_assignment_expr, input)
-        .expect("Could not parse compound assignment expression")
-        .next()
-        .expect("Could not get compound assignment expression");
-    let (start, end) = get_pair_location(&ast);
+pub fn parse_compound_assign_expr(ast: Pair<Rule>) -> DashlangResult<AssignmentExpr> {
+    let location = get_pair_location(&ast);
     let mut ast_inner = ast.into_inner();
 
-    let ast_symbol = ast_inner
-        .next()
-        .expect("Could not get symbol from compound assignment expression");
-    let (symbol_start, symbol_end) = get_pair_location(&ast_symbol);
-    let ast_operator = ast_inner
-        .next()
-        .expect("Could not get operator from compound assignment");
-    let ast_operand = ast_inner
-        .next()
-        .expect("Could not get operand operator from compound assignment");
-    let (start_ast_operand, _) = get_pair_location(&ast_operand);
-    let parsed_ast_operand =
-        parse_expression(ast_operand.as_str(), start_ast_operand + base_location)?;
+    let ast_target = next_pair(
+        &mut ast_inner,
+        location,
+        "target from compound assignment expression",
+    )?;
+    let target = parse_assignment_target(ast_target)?;
+    let ast_operator = next_pair(
+        &mut ast_inner,
+        location,
+        "operator from compound assignment",
+    )?;
+    let ast_operand = next_pair(
+        &mut ast_inner,
+        location,
+        "operand operator from compound assignment",
+    )?;
     Ok(AssignmentExpr {
-        symbol: ast_symbol.as_str().to_owned(),
-        value: Box::new(Expr::BinaryExpr(Box::new(BinaryExpr {
-            left: Expr::Symbol(Symbol {
-                value: ast_symbol.as_str().to_owned(),
-                location: Location::new(symbol_start + base_location, symbol_end + base_location),
-            }),
-            right: parsed_ast_operand,
-            operator: parse_binary_operator(ast_operator.as_str())?,
-            location: Location::new(start + base_location, end + base_location),
-        }))),
-        location: Location::new(start + base_location, end + base_location),
+        symbol: target.symbol,
+        path: target.path,
+        operator: Some(parse_binary_operator(ast_operator.as_str())?),
+        value: Box::new(parse_expression(ast_operand)?),
+        location,
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use ast::{BinaryExpr, BinaryOperator, Int, Literal, Symbol};
+    use ast::{BinaryOperator, Expr, Int, Literal, Location, Str};
 
     use super::*;
+    use crate::utils::parse_rule;
     #[test]
     fn test_compound_assignment() {
         assert_eq!(
-            parse_compound_assign_expr("n += 1", 0),
+            parse_compound_assign_expr(parse_rule(Rule::compound_assignment_expr, "n += 1")),
             Ok(AssignmentExpr {
                 symbol: String::from("n"),
-                value: Box::new(Expr::BinaryExpr(Box::new(BinaryExpr {
-                    left: Expr::Symbol(Symbol {
-                        value: String::from("n"),
-                        location: Location::new(0, 1)
-                    }),
-                    right: Expr::Literal(Literal::Int(Int {
-                        value: 1,
-                        location: Location::new(5, 6)
-                    })),
-                    operator: BinaryOperator::Add,
-                    location: Location::new(0, 6),
+                path: vec![],
+                operator: Some(BinaryOperator::Add),
+                value: Box::new(Expr::Literal(Literal::Int(Int {
+                    value: 1,
+                    location: Location::new(5, 6)
                 }))),
                 location: Location::new(0, 6),
             })
         );
         assert_eq!(
-            parse_compound_assign_expr("x -= 5", 0),
+            parse_compound_assign_expr(parse_rule(Rule::compound_assignment_expr, "x -= 5")),
             Ok(AssignmentExpr {
                 symbol: String::from("x"),
-                value: Box::new(Expr::BinaryExpr(Box::new(BinaryExpr {
-                    left: Expr::Symbol(Symbol {
-                        value: String::from("x"),
-                        location: Location::new(0, 1)
-                    }),
-                    right: Expr::Literal(Literal::Int(Int {
-                        value: 5,
-                        location: Location::new(5, 6)
-                    })),
-                    operator: BinaryOperator::Sub,
-                    location: Location::new(0, 6),
+                path: vec![],
+                operator: Some(BinaryOperator::Sub),
+                value: Box::new(Expr::Literal(Literal::Int(Int {
+                    value: 5,
+                    location: Location::new(5, 6)
                 }))),
                 location: Location::new(0, 6),
             })
         );
     }
+    #[test]
+    fn test_compound_path_assignment() {
+        assert_eq!(
+            parse_compound_assign_expr(parse_rule(Rule::compound_assignment_expr, "m.count += 1")),
+            Ok(AssignmentExpr {
+                symbol: String::from("m"),
+                path: vec![Expr::Literal(Literal::String(Str {
+                    value: String::from("count"),
+                    location: Location::new(2, 7)
+                }))],
+                operator: Some(BinaryOperator::Add),
+                value: Box::new(Expr::Literal(Literal::Int(Int {
+                    value: 1,
+                    location: Location::new(11, 12)
+                }))),
+                location: Location::new(0, 12),
+            })
+        );
+    }
+    #[test]
+    fn test_compound_multi_char_operator_assignment() {
+        for (input, operator) in [
+            ("x <<= 2", BinaryOperator::BitwiseShiftLeft),
+            ("x >>= 2", BinaryOperator::BitwiseShiftRight),
+            ("x **= 2", BinaryOperator::Pow),
+            ("x ~/= 2", BinaryOperator::IntDiv),
+        ] {
+            assert_eq!(
+                parse_compound_assign_expr(parse_rule(Rule::compound_assignment_expr, input)),
+                Ok(AssignmentExpr {
+                    symbol: String::from("x"),
+                    path: vec![],
+                    operator: Some(operator),
+                    value: Box::new(Expr::Literal(Literal::Int(Int {
+                        value: 2,
+                        location: Location::new(6, 7)
+                    }))),
+                    location: Location::new(0, 7),
+                })
+            );
+        }
+    }
 }
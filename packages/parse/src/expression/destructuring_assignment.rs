@@ -1,68 +1,58 @@
-use ast::{DestructuringAsignment, Symbol};
+use ast::DestructuringAsignment;
 use errors::DashlangResult;
-use pest::Parser;
+use pest::iterators::Pair;
 
 use crate::{
-    parser::{DashlangParser, Rule},
-    utils::get_pair_location,
+    parser::Rule,
+    pattern::parse_pattern,
+    utils::{get_pair_location, next_pair},
 };
 
 use super::parse_expression;
 
-pub fn parse_destructuring_assignment(
-    input: &str,
-    base_location: usize,
-) -> DashlangResult<DestructuringAsignment> {
-    let ast = DashlangParser::parse(Rule::destructuring_assignment, input)
-        .expect("Could not parse tuple destructuring")
-        .next()
-        .expect("Could not parse tuple destructuring");
-    let (start, end) = get_pair_location(&ast);
+pub fn parse_destructuring_assignment(ast: Pair<Rule>) -> DashlangResult<DestructuringAsignment> {
+    let location = get_pair_location(&ast);
     let mut ast_inner = ast.into_inner();
-    let ast_symbols = ast_inner.next().expect("Could not get symbols");
-    let ast_value = ast_inner.next().expect("Could not get value");
-    let (ast_value_start, _) = get_pair_location(&ast_value);
+    let ast_pattern = next_pair(&mut ast_inner, location, "pattern")?;
+    let ast_value = next_pair(&mut ast_inner, location, "value")?;
     Ok(DestructuringAsignment {
-        location: (start + base_location, end + base_location).into(),
-        symbols: ast_symbols
-            .into_inner()
-            .map(|element| {
-                let (element_start, element_end) = get_pair_location(&element);
-                Symbol {
-                    value: element.as_str().to_owned(),
-                    location: (element_start + base_location, element_end + base_location).into(),
-                }
-            })
-            .collect(),
-        value: Box::new(parse_expression(
-            ast_value.as_str(),
-            ast_value_start + base_location,
-        )?),
+        location,
+        pattern: parse_pattern(ast_pattern)?,
+        value: Box::new(parse_expression(ast_value)?),
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use ast::{Expr, Literal, Symbol, Tuple};
+    use ast::{
+        Expr, Literal, MapPattern, Pattern, RestPattern, Symbol, Tuple, TuplePattern, VectorPattern,
+    };
 
     use super::*;
+    use crate::utils::parse_rule;
 
     #[test]
     fn test_parse_destructuring_assignment() {
         assert_eq!(
-            parse_destructuring_assignment("(first, second) = (name, age)", 0),
+            parse_destructuring_assignment(parse_rule(
+                Rule::destructuring_assignment,
+                "(first, second) = (name, age)"
+            )),
             Ok(DestructuringAsignment {
                 location: (0, 29).into(),
-                symbols: vec![
-                    Symbol {
-                        value: "first".to_owned(),
-                        location: (1, 6).into()
-                    },
-                    Symbol {
-                        value: "second".to_owned(),
-                        location: (8, 14).into()
-                    }
-                ],
+                pattern: Pattern::Tuple(TuplePattern {
+                    items: vec![
+                        Pattern::Binding(Symbol {
+                            value: "first".to_owned(),
+                            location: (1, 6).into()
+                        }),
+                        Pattern::Binding(Symbol {
+                            value: "second".to_owned(),
+                            location: (8, 14).into()
+                        })
+                    ],
+                    location: (0, 15).into()
+                }),
                 value: Box::new(Expr::Literal(Literal::Tuple(Tuple {
                     value: vec![
                         Expr::Symbol(Symbol {
@@ -79,4 +69,65 @@ mod tests {
             })
         );
     }
+    #[test]
+    fn test_parse_nested_destructuring() {
+        let parsed = parse_destructuring_assignment(parse_rule(
+            Rule::destructuring_assignment,
+            "[(key, _), ...rest] = entries",
+        ))
+        .map(|assignment| assignment.pattern);
+        assert_eq!(
+            parsed,
+            Ok(Pattern::Vector(VectorPattern {
+                items: vec![Pattern::Tuple(TuplePattern {
+                    items: vec![
+                        Pattern::Binding(Symbol {
+                            value: "key".to_owned(),
+                            location: (2, 5).into()
+                        }),
+                        Pattern::Wildcard((7, 8).into()),
+                    ],
+                    location: (1, 9).into()
+                })],
+                rest: Some(RestPattern {
+                    symbol: Some(Symbol {
+                        value: "rest".to_owned(),
+                        location: (14, 18).into()
+                    }),
+                    location: (11, 18).into()
+                }),
+                location: (0, 19).into()
+            }))
+        );
+    }
+    #[test]
+    fn test_parse_map_destructuring() {
+        let parsed = parse_destructuring_assignment(parse_rule(
+            Rule::destructuring_assignment,
+            "{name, age} = person",
+        ))
+        .map(|assignment| assignment.pattern);
+        assert_eq!(
+            parsed,
+            Ok(Pattern::Map(MapPattern {
+                fields: vec![
+                    (
+                        "name".to_owned(),
+                        Pattern::Binding(Symbol {
+                            value: "name".to_owned(),
+                            location: (1, 5).into()
+                        })
+                    ),
+                    (
+                        "age".to_owned(),
+                        Pattern::Binding(Symbol {
+                            value: "age".to_owned(),
+                            location: (7, 10).into()
+                        })
+                    ),
+                ],
+                location: (0, 11).into()
+            }))
+        );
+    }
 }
@@ -0,0 +1,118 @@
+use ast::{Match, MatchArm};
+use errors::DashlangResult;
+use pest::iterators::Pair;
+
+use crate::{
+    parser::Rule,
+    pattern::parse_pattern,
+    utils::{get_pair_location, inner_pair, next_pair},
+};
+
+use super::parse_expression;
+
+fn parse_match_arm(ast: Pair<Rule>) -> DashlangResult<MatchArm> {
+    let location = get_pair_location(&ast);
+    let mut ast_inner = ast.into_inner();
+    let pattern = parse_pattern(next_pair(&mut ast_inner, location, "match arm pattern")?)?;
+    let mut value = next_pair(&mut ast_inner, location, "match arm value")?;
+    let mut guard = None;
+    if value.as_rule() == Rule::match_guard {
+        guard = Some(parse_expression(inner_pair(value, "match guard")?)?);
+        value = next_pair(&mut ast_inner, location, "match arm value")?;
+    }
+    Ok(MatchArm {
+        pattern,
+        guard,
+        value: parse_expression(value)?,
+        location,
+    })
+}
+
+pub fn parse_match_expression(ast: Pair<Rule>) -> DashlangResult<Match> {
+    let location = get_pair_location(&ast);
+    let mut ast_inner = ast.into_inner();
+    // Skips the `match` keyword
+    next_pair(&mut ast_inner, location, "`match` keyword")?;
+    let value = parse_expression(next_pair(&mut ast_inner, location, "matched value")?)?;
+    Ok(Match {
+        value,
+        arms: ast_inner
+            .map(parse_match_arm)
+            .collect::<DashlangResult<_>>()?,
+        location,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_rule;
+    use ast::{
+        Atom, BinaryExpr, BinaryOperator, Expr, Int, Literal, Location, Pattern, Symbol,
+        TuplePattern,
+    };
+
+    fn symbol(value: &str, start: usize, end: usize) -> Symbol {
+        Symbol {
+            value: String::from(value),
+            location: Location::new(start, end),
+        }
+    }
+
+    #[test]
+    fn test_parse_match_expression() {
+        let source = "match result { (:ok, n) if n > 0 -> n, _ -> 0, }";
+        assert_eq!(
+            parse_match_expression(parse_rule(Rule::match_expression, source)),
+            Ok(Match {
+                value: Expr::Symbol(symbol("result", 6, 12)),
+                arms: vec![
+                    MatchArm {
+                        pattern: Pattern::Tuple(TuplePattern {
+                            items: vec![
+                                Pattern::Literal(Literal::Atom(Atom {
+                                    value: String::from("ok"),
+                                    location: Location::new(16, 19)
+                                })),
+                                Pattern::Binding(symbol("n", 21, 22)),
+                            ],
+                            location: Location::new(15, 23),
+                        }),
+                        guard: Some(Expr::BinaryExpr(Box::new(BinaryExpr {
+                            left: Expr::Symbol(symbol("n", 27, 28)),
+                            right: Expr::Literal(Literal::Int(Int {
+                                value: 0,
+                                location: Location::new(31, 32)
+                            })),
+                            operator: BinaryOperator::Gt,
+                            location: Location::new(27, 32),
+                        }))),
+                        value: Expr::Symbol(symbol("n", 36, 37)),
+                        location: Location::new(15, 37),
+                    },
+                    MatchArm {
+                        pattern: Pattern::Wildcard(Location::new(39, 40)),
+                        guard: None,
+                        value: Expr::Literal(Literal::Int(Int {
+                            value: 0,
+                            location: Location::new(44, 45)
+                        })),
+                        location: Location::new(39, 45),
+                    },
+                ],
+                location: Location::new(0, 48),
+            })
+        );
+    }
+    #[test]
+    fn test_match_is_an_operand() {
+        let program = crate::program::parse_program(parse_rule(
+            Rule::program,
+            "size = 1 + match items { [] -> 0, [_, ...rest] -> len(rest) }",
+        ));
+        assert!(matches!(
+            program.as_deref(),
+            Ok([ast::Stmt::Expr(Expr::Assignment(_))])
+        ));
+    }
+}
@@ -1,122 +1,92 @@
-use crate::{literal::parse_literal, utils::get_pair_location, DashlangParser, Rule};
+use crate::{
+    parser::Rule,
+    utils::{get_pair_location, inner_pair, next_pair, unexpected_pair},
+};
 
-use errors::{DashlangError, DashlangResult, ErrorKind};
+use errors::DashlangResult;
 
-use ast::{Expr, Location, SubExpr, Symbol};
-use pest::Parser;
+use ast::{Expr, SubExpr, Symbol};
+use pest::iterators::Pair;
 
 use self::{
-    assignment_expression::parse_assignment_expression, binary_expression::parse_binary_expression,
-    call_expression::parse_call_expression, compound_assign_expr::parse_compound_assign_expr,
-    dash_expression::parse_dash_expression,
+    assignment_expression::parse_assignment_expression, call_expression::parse_call_expression,
+    compound_assign_expr::parse_compound_assign_expr, dash_expression::parse_dash_expression,
     destructuring_assignment::parse_destructuring_assignment,
-    unary_expression::parse_unary_expression,
+    range_expression::parse_range_expression,
 };
 
 mod assignment_expression;
 mod binary_expression;
 mod binary_operator;
+mod block_expression;
 mod call_expression;
 mod compound_assign_expr;
 mod dash_expression;
 mod destructuring_assignment;
+mod match_expression;
+mod postfix_expression;
+mod range_expression;
+mod string_interpolation;
 mod unary_expression;
 
-pub fn parse_expression(input: &str, base_location: usize) -> DashlangResult<Expr> {
-    let ast = DashlangParser::parse(Rule::expression, input)
-        .map_err(|err| DashlangError {
-            location: match err.location {
-                pest::error::InputLocation::Pos(_) => None,
-                pest::error::InputLocation::Span((start, end)) => Some(Location { start, end }),
-            },
-            message: format!("Could not parse expression: {}", err),
-            kind: ErrorKind::Unknown,
-        })?
-        .next()
-        .expect("Could not parse expression");
-    let (start, end) = get_pair_location(&ast);
+pub fn parse_symbol(ast: Pair<Rule>) -> Symbol {
+    Symbol {
+        location: get_pair_location(&ast),
+        value: ast.as_str().to_owned(),
+    }
+}
+
+pub fn parse_expression(ast: Pair<Rule>) -> DashlangResult<Expr> {
+    let location = get_pair_location(&ast);
     let mut inner_ast = ast.into_inner();
-    let expression = inner_ast.next().expect("Could not get expression type");
+    let expression = next_pair(&mut inner_ast, location, "expression type")?;
     let mut parsed = match expression.as_rule() {
-        Rule::binary_expression => {
-            let parsed = parse_binary_expression(expression.as_str(), start + base_location)?;
-            Expr::BinaryExpr(Box::new(parsed))
-        }
-        Rule::assignment_expression => {
-            let parsed = parse_assignment_expression(expression.as_str(), start + base_location)?;
-            Expr::Assignment(parsed)
-        }
-        Rule::compound_assignment_expr => Expr::Assignment(parse_compound_assign_expr(
-            expression.as_str(),
-            start + base_location,
-        )?),
-        Rule::call_expression => {
-            let parsed = parse_call_expression(expression.as_str(), start + base_location)?;
-            Expr::Call(parsed)
-        }
-        Rule::symbol => Expr::Symbol(Symbol {
-            value: expression.as_str().to_owned(),
-            location: Location::new(start + base_location, end + base_location),
-        }),
-        Rule::literal => Expr::Literal(parse_literal(expression.as_str(), start + base_location)?),
-        Rule::unary_expression => Expr::UnaryExpr(Box::new(parse_unary_expression(
-            expression.as_str(),
-            start + base_location,
-        )?)),
+        Rule::range_expression => parse_range_expression(expression)?,
+        Rule::assignment_expression => Expr::Assignment(parse_assignment_expression(expression)?),
+        Rule::compound_assignment_expr => Expr::Assignment(parse_compound_assign_expr(expression)?),
         Rule::destructuring_assignment => {
-            let parsed =
-                parse_destructuring_assignment(expression.as_str(), start + base_location)?;
-            Expr::DestructuringAsignment(parsed)
-        }
-        Rule::dash_expr => {
-            let parsed = parse_dash_expression(expression.as_str(), start + base_location)?;
-            Expr::Assignment(parsed)
+            Expr::DestructuringAsignment(parse_destructuring_assignment(expression)?)
         }
-        any => unreachable!("{:#?}", any),
+        Rule::dash_expr => Expr::Assignment(parse_dash_expression(expression)?),
+        _ => return Err(unexpected_pair(&expression)),
     };
     for piping in inner_ast {
-        let inner_call = piping
-            .into_inner()
-            .next()
-            .expect("Could not get call from piping");
-        let (start_call, _end) = get_pair_location(&inner_call);
-        let mut parsed_inner_call =
-            parse_call_expression(inner_call.as_str(), start_call + base_location)?;
+        let inner_call = inner_pair(piping, "call from piping")?;
+        let mut parsed_inner_call = parse_call_expression(inner_call)?;
         parsed_inner_call.args.insert(0, parsed);
         parsed = Expr::Call(parsed_inner_call);
     }
     Ok(parsed)
 }
-pub fn parse_sub_expression(input: &str, base_location: usize) -> DashlangResult<SubExpr> {
-    let ast = DashlangParser::parse(Rule::sub_expression, input)
-        .expect("Could not parse sub expression")
-        .next()
-        .expect("Could not parse sub expression");
-    let (start, end) = get_pair_location(&ast);
-    let ast_expr = ast
-        .into_inner()
-        .next()
-        .expect("Could not get expression from sub expression");
-    let ast_expr_start = ast_expr.as_span().start();
-    let parsed = parse_expression(ast_expr.as_str(), ast_expr_start + base_location)?;
-    let absolute_location = (start + base_location, end + base_location).into();
-
+/// Conditions are expressions, except for `(cond)` right before a body, which is kept as the
+/// bracketed expression alone instead of a closure
+pub fn parse_condition(ast: Pair<Rule>) -> DashlangResult<Expr> {
+    match ast.as_rule() {
+        Rule::sub_expression => Ok(Expr::SubExpr(parse_sub_expression(ast)?)),
+        _ => parse_expression(ast),
+    }
+}
+pub fn parse_sub_expression(ast: Pair<Rule>) -> DashlangResult<SubExpr> {
+    let location = get_pair_location(&ast);
+    let ast_expr = inner_pair(ast, "expression from sub expression")?;
     Ok(SubExpr {
-        value: Box::new(parsed),
-        location: absolute_location,
+        value: Box::new(parse_expression(ast_expr)?),
+        location,
     })
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::parse_rule;
     use ast::{
-        AssignmentExpr, BinaryExpr, BinaryOperator, Boolean, Call, Expr, Int, Literal, Location,
-        UnaryExpr,
+        Access, AssignmentExpr, BinaryExpr, BinaryOperator, Boolean, Call, Expr, If, Int, Literal,
+        Location, Stmt, Str, UnaryExpr,
     };
     #[test]
     fn test_parse_expression() {
         assert_eq!(
-            parse_expression("1 + 2", 0),
+            parse_expression(parse_rule(Rule::expression, "1 + 2")),
             Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
                 left: Expr::Literal(Literal::Int(Int {
                     value: 1,
@@ -132,11 +102,91 @@ mod tests {
         );
     }
     #[test]
+    fn test_expression_with_comments() {
+        assert_eq!(
+            parse_expression(parse_rule(
+                Rule::expression,
+                "count /* lhs */ < // rhs\n 10"
+            )),
+            Ok(Expr::BinaryExpr(Box::new(BinaryExpr {
+                left: Expr::Symbol(Symbol {
+                    value: String::from("count"),
+                    location: Location::new(0, 5)
+                }),
+                right: Expr::Literal(Literal::Int(Int {
+                    value: 10,
+                    location: Location::new(26, 28)
+                })),
+                operator: BinaryOperator::Lt,
+                location: Location::new(0, 28),
+            })))
+        );
+        assert_eq!(
+            parse_expression(parse_rule(Rule::expression, "x /* piped */ |> add(1)")),
+            Ok(Expr::Call(Call {
+                callee: Box::new(Expr::Symbol(Symbol {
+                    value: String::from("add"),
+                    location: Location::new(17, 20)
+                })),
+                args: vec![
+                    Expr::Symbol(Symbol {
+                        value: String::from("x"),
+                        location: Location::new(0, 1)
+                    }),
+                    Expr::Literal(Literal::Int(Int {
+                        value: 1,
+                        location: Location::new(21, 22)
+                    }))
+                ],
+                location: Location::new(17, 23),
+            }))
+        );
+    }
+    #[test]
+    fn test_access_in_pipe() {
+        assert_eq!(
+            parse_expression(parse_rule(Rule::expression, "items[0] |> add(m.count)")),
+            Ok(Expr::Call(Call {
+                callee: Box::new(Expr::Symbol(Symbol {
+                    value: String::from("add"),
+                    location: Location::new(12, 15)
+                })),
+                args: vec![
+                    Expr::Access(Box::new(Access {
+                        target: Expr::Symbol(Symbol {
+                            value: String::from("items"),
+                            location: Location::new(0, 5)
+                        }),
+                        key: Expr::Literal(Literal::Int(Int {
+                            value: 0,
+                            location: Location::new(6, 7)
+                        })),
+                        location: Location::new(0, 8),
+                    })),
+                    Expr::Access(Box::new(Access {
+                        target: Expr::Symbol(Symbol {
+                            value: String::from("m"),
+                            location: Location::new(16, 17)
+                        }),
+                        key: Expr::Literal(Literal::String(Str {
+                            value: String::from("count"),
+                            location: Location::new(18, 23)
+                        })),
+                        location: Location::new(16, 23),
+                    })),
+                ],
+                location: Location::new(12, 24),
+            }))
+        );
+    }
+    #[test]
     fn test_assignment_expression() {
         assert_eq!(
-            parse_expression("age = 5 + 1", 0),
+            parse_expression(parse_rule(Rule::expression, "age = 5 + 1")),
             Ok(Expr::Assignment(AssignmentExpr {
                 symbol: String::from("age"),
+                path: vec![],
+                operator: None,
                 value: Box::new(Expr::BinaryExpr(Box::new(BinaryExpr {
                     left: Expr::Literal(Literal::Int(Int {
                         value: 5,
@@ -154,9 +204,51 @@ mod tests {
         );
     }
     #[test]
+    fn test_if_expression() {
+        assert_eq!(
+            parse_expression(parse_rule(Rule::expression, "a = if ok { 1 } else { 2 }")),
+            Ok(Expr::Assignment(AssignmentExpr {
+                symbol: String::from("a"),
+                path: vec![],
+                operator: None,
+                value: Box::new(Expr::If(Box::new(If {
+                    cond: Expr::Symbol(Symbol {
+                        value: String::from("ok"),
+                        location: Location::new(7, 9)
+                    }),
+                    body: vec![Stmt::Expr(Expr::Literal(Literal::Int(Int {
+                        value: 1,
+                        location: Location::new(12, 13)
+                    })))],
+                    else_block: Some(vec![Stmt::Expr(Expr::Literal(Literal::Int(Int {
+                        value: 2,
+                        location: Location::new(23, 24)
+                    })))]),
+                    location: Location::new(4, 26),
+                }))),
+                location: Location::new(0, 26),
+            }))
+        );
+    }
+    #[test]
+    fn test_braces_are_a_map_before_a_block() {
+        assert!(matches!(
+            parse_expression(parse_rule(Rule::expression, "{}")),
+            Ok(Expr::Literal(Literal::Map(_)))
+        ));
+        assert!(matches!(
+            parse_expression(parse_rule(Rule::expression, "{a: 1}")),
+            Ok(Expr::Literal(Literal::Map(_)))
+        ));
+        assert!(matches!(
+            parse_expression(parse_rule(Rule::expression, "{a}")),
+            Ok(Expr::Block(_))
+        ));
+    }
+    #[test]
     fn test_unary_expression() {
         assert_eq!(
-            parse_expression("!(true && false)", 0),
+            parse_expression(parse_rule(Rule::expression, "!(true && false)")),
             Ok(Expr::UnaryExpr(Box::new(UnaryExpr {
                 operator: ast::UnaryOperator::Not,
                 operand: Expr::SubExpr(SubExpr {
@@ -181,20 +273,14 @@ mod tests {
     #[test]
     fn test_compound_assign_expr() {
         assert_eq!(
-            parse_expression("n += 1", 0),
+            parse_expression(parse_rule(Rule::expression, "n += 1")),
             Ok(Expr::Assignment(AssignmentExpr {
                 symbol: String::from("n"),
-                value: Box::new(Expr::BinaryExpr(Box::new(BinaryExpr {
-                    left: Expr::Symbol(Symbol {
-                        value: String::from("n"),
-                        location: Location::new(0, 1)
-                    }),
-                    right: Expr::Literal(Literal::Int(Int {
-                        value: 1,
-                        location: Location::new(5, 6)
-                    })),
-                    operator: BinaryOperator::Add,
-                    location: Location::new(0, 6),
+                path: vec![],
+                operator: Some(BinaryOperator::Add),
+                value: Box::new(Expr::Literal(Literal::Int(Int {
+                    value: 1,
+                    location: Location::new(5, 6)
                 }))),
                 location: Location::new(0, 6),
             }))
@@ -203,9 +289,12 @@ mod tests {
     #[test]
     fn test_piping() {
         assert_eq!(
-            parse_expression("4 |> add(1)", 0),
+            parse_expression(parse_rule(Rule::expression, "4 |> add(1)")),
             Ok(Expr::Call(Call {
-                symbol: String::from("add"),
+                callee: Box::new(Expr::Symbol(Symbol {
+                    value: String::from("add"),
+                    location: Location::new(5, 8)
+                })),
                 args: vec![
                     Expr::Literal(Literal::Int(Int {
                         value: 4,
@@ -220,4 +309,34 @@ mod tests {
             }))
         );
     }
+    #[test]
+    fn test_piping_into_chained_call() {
+        assert_eq!(
+            parse_expression(parse_rule(Rule::expression, "4 |> adders[0](1)")),
+            Ok(Expr::Call(Call {
+                callee: Box::new(Expr::Access(Box::new(Access {
+                    target: Expr::Symbol(Symbol {
+                        value: String::from("adders"),
+                        location: Location::new(5, 11)
+                    }),
+                    key: Expr::Literal(Literal::Int(Int {
+                        value: 0,
+                        location: Location::new(12, 13)
+                    })),
+                    location: Location::new(5, 14)
+                }))),
+                args: vec![
+                    Expr::Literal(Literal::Int(Int {
+                        value: 4,
+                        location: Location::new(0, 1)
+                    })),
+                    Expr::Literal(Literal::Int(Int {
+                        value: 1,
+                        location: Location::new(15, 16)
+                    }))
+                ],
+                location: Location::new(5, 17),
+            }))
+        );
+    }
 }
@@ -0,0 +1,186 @@
+use ast::{Access, Call, Expr, Int, Literal, Location, Str};
+use errors::{DashlangError, DashlangResult, ErrorKind};
+use pest::iterators::Pair;
+
+use crate::{
+    literal::parse_literal,
+    parser::Rule,
+    utils::{get_pair_location, inner_pair, next_pair, unexpected_pair},
+};
+
+use super::{
+    parse_expression, parse_sub_expression, parse_symbol,
+    string_interpolation::parse_string_interpolation,
+};
+
+pub fn parse_accessor(accessor: Pair<Rule>) -> DashlangResult<Expr> {
+    let ast_key = inner_pair(accessor, "accessed key")?;
+    let location = get_pair_location(&ast_key);
+    match ast_key.as_rule() {
+        Rule::expression => parse_expression(ast_key),
+        Rule::symbol => Ok(Expr::Literal(Literal::String(Str {
+            value: ast_key.as_str().to_owned(),
+            location,
+        }))),
+        Rule::tuple_index => Ok(Expr::Literal(Literal::Int(Int {
+            value: ast_key.as_str().parse().map_err(|_| {
+                DashlangError::new("Invalid tuple index", ErrorKind::InvalidOperation)
+                    .location(location)
+            })?,
+            location,
+        }))),
+        _ => Err(unexpected_pair(&ast_key)),
+    }
+}
+
+fn parse_call_args(call_args: Pair<Rule>) -> DashlangResult<Vec<Expr>> {
+    call_args
+        .into_inner()
+        .map(|element| {
+            let inner_arg = inner_pair(element, "call arg content")?;
+            parse_expression(inner_arg)
+        })
+        .collect()
+}
+
+/// Works for both `postfix_expression` and `call_expression`
+pub fn parse_postfix_expression(ast: Pair<Rule>) -> DashlangResult<Expr> {
+    let location = get_pair_location(&ast);
+    let mut ast_inner = ast.into_inner();
+    let ast_target = next_pair(&mut ast_inner, location, "postfix expression target")?;
+    let start = get_pair_location(&ast_target).start;
+    let mut parsed = match ast_target.as_rule() {
+        Rule::interpolated_string => {
+            Expr::StringInterpolation(parse_string_interpolation(ast_target)?)
+        }
+        Rule::literal => Expr::Literal(parse_literal(ast_target)?),
+        Rule::sub_expression => Expr::SubExpr(parse_sub_expression(ast_target)?),
+        Rule::symbol => Expr::Symbol(parse_symbol(ast_target)),
+        _ => return Err(unexpected_pair(&ast_target)),
+    };
+    for postfix in ast_inner {
+        let location = Location::new(start, get_pair_location(&postfix).end);
+        parsed = match postfix.as_rule() {
+            Rule::call_args => Expr::Call(Call {
+                callee: Box::new(parsed),
+                args: parse_call_args(postfix)?,
+                location,
+            }),
+            _ => Expr::Access(Box::new(Access {
+                target: parsed,
+                key: parse_accessor(postfix)?,
+                location,
+            })),
+        };
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use ast::Symbol;
+
+    use super::*;
+    use crate::utils::parse_rule;
+
+    #[test]
+    fn test_parse_index_access() {
+        assert_eq!(
+            parse_postfix_expression(parse_rule(Rule::postfix_expression, "items[i]")),
+            Ok(Expr::Access(Box::new(Access {
+                target: Expr::Symbol(Symbol {
+                    value: "items".to_owned(),
+                    location: (0, 5).into()
+                }),
+                key: Expr::Symbol(Symbol {
+                    value: "i".to_owned(),
+                    location: (6, 7).into()
+                }),
+                location: (0, 8).into()
+            })))
+        );
+    }
+    #[test]
+    fn test_parse_chained_access() {
+        assert_eq!(
+            parse_postfix_expression(parse_rule(
+                Rule::postfix_expression,
+                r#"get_people()[0].name.0"#
+            )),
+            Ok(Expr::Access(Box::new(Access {
+                target: Expr::Access(Box::new(Access {
+                    target: Expr::Access(Box::new(Access {
+                        target: Expr::Call(Call {
+                            callee: Box::new(Expr::Symbol(Symbol {
+                                value: "get_people".to_owned(),
+                                location: (0, 10).into()
+                            })),
+                            args: vec![],
+                            location: (0, 12).into()
+                        }),
+                        key: Expr::Literal(Literal::Int(Int {
+                            value: 0,
+                            location: (13, 14).into()
+                        })),
+                        location: (0, 15).into()
+                    })),
+                    key: Expr::Literal(Literal::String(Str {
+                        value: "name".to_owned(),
+                        location: (16, 20).into()
+                    })),
+                    location: (0, 20).into()
+                })),
+                key: Expr::Literal(Literal::Int(Int {
+                    value: 0,
+                    location: (21, 22).into()
+                })),
+                location: (0, 22).into()
+            })))
+        );
+    }
+    #[test]
+    fn test_parse_chained_call() {
+        assert_eq!(
+            parse_postfix_expression(parse_rule(Rule::postfix_expression, "make_adder(1)(2)")),
+            Ok(Expr::Call(Call {
+                callee: Box::new(Expr::Call(Call {
+                    callee: Box::new(Expr::Symbol(Symbol {
+                        value: "make_adder".to_owned(),
+                        location: (0, 10).into()
+                    })),
+                    args: vec![Expr::Literal(Literal::Int(Int {
+                        value: 1,
+                        location: (11, 12).into()
+                    }))],
+                    location: (0, 13).into()
+                })),
+                args: vec![Expr::Literal(Literal::Int(Int {
+                    value: 2,
+                    location: (14, 15).into()
+                }))],
+                location: (0, 16).into()
+            }))
+        );
+    }
+    #[test]
+    fn test_parse_member_call() {
+        assert_eq!(
+            parse_postfix_expression(parse_rule(Rule::postfix_expression, "handlers.on_click()")),
+            Ok(Expr::Call(Call {
+                callee: Box::new(Expr::Access(Box::new(Access {
+                    target: Expr::Symbol(Symbol {
+                        value: "handlers".to_owned(),
+                        location: (0, 8).into()
+                    }),
+                    key: Expr::Literal(Literal::String(Str {
+                        value: "on_click".to_owned(),
+                        location: (9, 17).into()
+                    })),
+                    location: (0, 17).into()
+                }))),
+                args: vec![],
+                location: (0, 19).into()
+            }))
+        );
+    }
+}
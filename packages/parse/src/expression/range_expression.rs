@@ -0,0 +1,112 @@
+use ast::{Expr, Range};
+use errors::DashlangResult;
+use pest::iterators::Pair;
+
+use crate::{
+    parser::Rule,
+    utils::{get_pair_location, next_pair},
+};
+
+use super::binary_expression::parse_binary_expression;
+
+/// Ranges bind looser than any operator, so `0..n + 1` ends at `n + 1`
+pub fn parse_range_expression(ast: Pair<Rule>) -> DashlangResult<Expr> {
+    let location = get_pair_location(&ast);
+    let mut ast_inner = ast.into_inner();
+    let start = parse_binary_expression(next_pair(&mut ast_inner, location, "range start")?)?;
+    let Some(operator) = ast_inner.next() else {
+        return Ok(start);
+    };
+    let end = parse_binary_expression(next_pair(&mut ast_inner, location, "range end")?)?;
+    let step = match ast_inner.next() {
+        Some(range_step) => {
+            let step_location = get_pair_location(&range_step);
+            let mut step_inner = range_step.into_inner();
+            next_pair(&mut step_inner, step_location, "step keyword")?;
+            Some(parse_binary_expression(next_pair(
+                &mut step_inner,
+                step_location,
+                "range step",
+            )?)?)
+        }
+        None => None,
+    };
+    Ok(Expr::Range(Box::new(Range {
+        start,
+        end,
+        step,
+        inclusive: operator.as_rule() == Rule::range_inclusive,
+        location,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_rule;
+    use ast::{BinaryExpr, BinaryOperator, Int, Literal, Location, Symbol};
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(
+            parse_range_expression(parse_rule(Rule::range_expression, "0..n + 1")),
+            Ok(Expr::Range(Box::new(Range {
+                start: Expr::Literal(Literal::Int(Int {
+                    value: 0,
+                    location: Location::new(0, 1)
+                })),
+                end: Expr::BinaryExpr(Box::new(BinaryExpr {
+                    left: Expr::Symbol(Symbol {
+                        value: String::from("n"),
+                        location: Location::new(3, 4)
+                    }),
+                    right: Expr::Literal(Literal::Int(Int {
+                        value: 1,
+                        location: Location::new(7, 8)
+                    })),
+                    operator: BinaryOperator::Add,
+                    location: Location::new(3, 8),
+                })),
+                step: None,
+                inclusive: false,
+                location: Location::new(0, 8),
+            })))
+        );
+    }
+    #[test]
+    fn test_parse_inclusive_range_with_step() {
+        let int = |value, start, end| {
+            Expr::Literal(Literal::Int(Int {
+                value,
+                location: Location::new(start, end),
+            }))
+        };
+        assert_eq!(
+            parse_range_expression(parse_rule(Rule::range_expression, "10..=0 step -2")),
+            Ok(Expr::Range(Box::new(Range {
+                start: int(10, 0, 2),
+                end: int(0, 5, 6),
+                step: Some(int(-2, 12, 14)),
+                inclusive: true,
+                location: Location::new(0, 14),
+            })))
+        );
+    }
+    #[test]
+    fn test_step_is_reserved() {
+        use crate::parser::DashlangParser;
+        use pest::Parser;
+
+        assert!(DashlangParser::parse(Rule::file, "numbers = 0..3\nstep = 2").is_err());
+    }
+    #[test]
+    fn test_parse_expression_without_range() {
+        assert_eq!(
+            parse_range_expression(parse_rule(Rule::range_expression, "1.5")),
+            Ok(Expr::Literal(Literal::Float(ast::Float {
+                value: 1.5,
+                location: Location::new(0, 3)
+            })))
+        );
+    }
+}
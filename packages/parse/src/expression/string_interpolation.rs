@@ -0,0 +1,103 @@
+use ast::{Expr, Literal, Str, StringInterpolation};
+use errors::DashlangResult;
+use pest::iterators::Pair;
+
+use crate::{
+    literal::string::unescape,
+    parser::Rule,
+    utils::{get_pair_location, inner_pair, unexpected_pair},
+};
+
+use super::parse_expression;
+
+pub fn parse_string_interpolation(ast: Pair<Rule>) -> DashlangResult<StringInterpolation> {
+    let location = get_pair_location(&ast);
+    let mut parts = vec![];
+    for part in ast.into_inner() {
+        match part.as_rule() {
+            Rule::text => {
+                // Empty chunks between two interpolations add nothing to the output
+                if part.as_str().is_empty() {
+                    continue;
+                }
+                let part_location = get_pair_location(&part);
+                parts.push(Expr::Literal(Literal::String(Str {
+                    value: unescape(part.as_str(), part_location.start)?,
+                    location: part_location,
+                })));
+            }
+            Rule::interpolation => {
+                let ast_expr = inner_pair(part, "interpolated expression")?;
+                parts.push(parse_expression(ast_expr)?);
+            }
+            _ => return Err(unexpected_pair(&part)),
+        }
+    }
+    Ok(StringInterpolation { parts, location })
+}
+
+#[cfg(test)]
+mod tests {
+    use ast::{BinaryExpr, BinaryOperator, Int, Symbol};
+
+    use super::*;
+    use crate::utils::parse_rule;
+
+    #[test]
+    fn test_parse_interpolation() {
+        assert_eq!(
+            parse_string_interpolation(parse_rule(
+                Rule::interpolated_string,
+                r#""Hello ${name}, you are ${age + 1}""#
+            )),
+            Ok(StringInterpolation {
+                parts: vec![
+                    Expr::Literal(Literal::String(Str {
+                        value: "Hello ".to_owned(),
+                        location: (1, 7).into()
+                    })),
+                    Expr::Symbol(Symbol {
+                        value: "name".to_owned(),
+                        location: (9, 13).into()
+                    }),
+                    Expr::Literal(Literal::String(Str {
+                        value: ", you are ".to_owned(),
+                        location: (14, 24).into()
+                    })),
+                    Expr::BinaryExpr(Box::new(BinaryExpr {
+                        left: Expr::Symbol(Symbol {
+                            value: "age".to_owned(),
+                            location: (26, 29).into()
+                        }),
+                        right: Expr::Literal(Literal::Int(Int {
+                            value: 1,
+                            location: (32, 33).into()
+                        })),
+                        operator: BinaryOperator::Add,
+                        location: (26, 33).into()
+                    })),
+                ],
+                location: (0, 35).into()
+            })
+        );
+    }
+    #[test]
+    fn test_parse_escaped_interpolation() {
+        assert_eq!(
+            parse_string_interpolation(parse_rule(Rule::interpolated_string, r#""${n}\${n}\n""#)),
+            Ok(StringInterpolation {
+                parts: vec![
+                    Expr::Symbol(Symbol {
+                        value: "n".to_owned(),
+                        location: (3, 4).into()
+                    }),
+                    Expr::Literal(Literal::String(Str {
+                        value: "${n}\n".to_owned(),
+                        location: (5, 12).into()
+                    })),
+                ],
+                location: (0, 13).into()
+            })
+        );
+    }
+}
@@ -1,64 +1,53 @@
-use ast::{Expr, Location, UnaryExpr, UnaryOperator};
-use errors::{DashlangError, DashlangResult, ErrorKind};
-use pest::Parser;
+use ast::{Expr, UnaryExpr, UnaryOperator};
+use errors::DashlangResult;
+use pest::iterators::Pair;
 
 use crate::{
-    parser::{DashlangParser, Rule},
-    utils::get_pair_location,
+    parser::Rule,
+    utils::{enter_expression, get_pair_location, inner_pair, next_pair, unexpected_pair},
 };
 
-use super::{parse_expression, parse_sub_expression};
+use super::postfix_expression::parse_postfix_expression;
 
-fn parse_unary_operator(input: &str) -> DashlangResult<UnaryOperator> {
-    match input {
-        "!" => Ok(UnaryOperator::Not),
-        "~" => Ok(UnaryOperator::BitwiseNot),
-        _ => Err(DashlangError::new(
-            "Invalid unary operator",
-            ErrorKind::Unknown,
-        )),
+fn parse_unary_operator(ast: Pair<Rule>) -> DashlangResult<UnaryOperator> {
+    let operator = inner_pair(ast, "unary operator")?;
+    match operator.as_rule() {
+        Rule::unary_not => Ok(UnaryOperator::Not),
+        Rule::unary_bitwise_not => Ok(UnaryOperator::BitwiseNot),
+        Rule::unary_neg => Ok(UnaryOperator::Neg),
+        _ => Err(unexpected_pair(&operator)),
     }
 }
 
-pub fn parse_unary_expression(input: &str, base_location: usize) -> DashlangResult<UnaryExpr> {
-    let parsed = DashlangParser::parse(Rule::unary_expression, input)
-        .expect("Could not parse unary expression")
-        .next()
-        .expect("Could not get unary expression");
-    let (start, end) = get_pair_location(&parsed);
-    let mut parsed_inner = parsed.into_inner();
-    let operator = parsed_inner
-        .next()
-        .expect("Could not get unary expression operator");
-    let operand = parsed_inner
-        .next()
-        .expect("Could not get unary expression operand");
-    let (operand_start, _) = get_pair_location(&operand);
+pub fn parse_unary_expression(ast: Pair<Rule>) -> DashlangResult<UnaryExpr> {
+    let location = get_pair_location(&ast);
+    let _depth = enter_expression(1, location)?;
+    let mut ast_inner = ast.into_inner();
+    let operator = next_pair(&mut ast_inner, location, "unary expression operator")?;
+    let operand = next_pair(&mut ast_inner, location, "unary expression operand")?;
     let parsed_operand = match operand.as_rule() {
-        Rule::expression => parse_expression(operand.as_str(), operand_start + base_location)?,
-        Rule::sub_expression => Expr::SubExpr(parse_sub_expression(
-            operand.as_str(),
-            operand_start + base_location,
-        )?),
-        _ => unreachable!(),
+        Rule::unary_expression => Expr::UnaryExpr(Box::new(parse_unary_expression(operand)?)),
+        Rule::postfix_expression => parse_postfix_expression(operand)?,
+        _ => return Err(unexpected_pair(&operand)),
     };
     Ok(UnaryExpr {
-        operator: parse_unary_operator(operator.as_str())?,
+        operator: parse_unary_operator(operator)?,
         operand: parsed_operand,
-        location: Location::new(start, end),
+        location,
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use ast::{BinaryExpr, BinaryOperator, Boolean, Expr, Literal, SubExpr};
+    use ast::{BinaryExpr, BinaryOperator, Boolean, Expr, Literal, Location, SubExpr, Symbol};
 
     use super::*;
+    use crate::utils::parse_rule;
 
     #[test]
     fn test_not_true() {
         assert_eq!(
-            parse_unary_expression("!true", 0),
+            parse_unary_expression(parse_rule(Rule::unary_expression, "!true")),
             Ok(UnaryExpr {
                 operator: ast::UnaryOperator::Not,
                 operand: Expr::Literal(Literal::Bool(Boolean {
@@ -72,7 +61,7 @@ mod tests {
     #[test]
     fn test_sub() {
         assert_eq!(
-            parse_unary_expression("!(true && false)", 0),
+            parse_unary_expression(parse_rule(Rule::unary_expression, "!(true && false)")),
             Ok(UnaryExpr {
                 operator: UnaryOperator::Not,
                 operand: Expr::SubExpr(SubExpr {
@@ -94,4 +83,32 @@ mod tests {
             })
         );
     }
+    #[test]
+    fn test_neg_with_space_and_parentheses() {
+        let x = |start| {
+            Expr::Symbol(Symbol {
+                value: String::from("x"),
+                location: Location::new(start, start + 1),
+            })
+        };
+        assert_eq!(
+            parse_unary_expression(parse_rule(Rule::unary_expression, "- x")),
+            Ok(UnaryExpr {
+                operator: UnaryOperator::Neg,
+                operand: x(2),
+                location: Location::new(0, 3),
+            })
+        );
+        assert_eq!(
+            parse_unary_expression(parse_rule(Rule::unary_expression, "-(x)")),
+            Ok(UnaryExpr {
+                operator: UnaryOperator::Neg,
+                operand: Expr::SubExpr(SubExpr {
+                    value: Box::new(x(2)),
+                    location: Location::new(1, 4),
+                }),
+                location: Location::new(0, 4),
+            })
+        );
+    }
 }
@@ -1,39 +1,271 @@
 use ast::{Location, Program};
-use errors::{DashlangError, DashlangResult, ErrorKind};
+use errors::{DashlangError, ErrorKind};
 use pest::Parser;
 
 use crate::{
     parser::{DashlangParser, Rule},
-    program::parse_program,
+    recovery::{skip_invalid_statement, too_deep_nesting},
+    statement::parse_statement,
+    syntax_error::from_pest_error,
+    utils::{inner_pair, next_pair},
+    validation::validate_loop_control,
 };
 
-pub fn parse_file(input: &str) -> DashlangResult<Program> {
-    let ast = DashlangParser::parse(Rule::file, input)
-        .map_err(|err| DashlangError {
-            location: match err.location {
-                pest::error::InputLocation::Pos(pos) => Some(Location::new(pos, pos + 1)),
-                pest::error::InputLocation::Span((start, end)) => Some(Location::new(start, end)),
-            },
-            message: err.to_string(),
-            kind: ErrorKind::Unknown,
-        })?
-        .next()
-        .expect("Could not parse program");
-    let ast_program = ast.into_inner().next().expect("Could not parse file");
-    let parsed = parse_program(ast_program.as_str())?;
-    Ok(parsed)
+/// How deeply brackets and prefix operators can be nested, low enough for the parser to fit in a
+/// thread's stack
+const MAX_NESTING: usize = 32;
+
+/// Syntax errors don't stop parsing: the statement that caused each one is skipped and parsing
+/// starts over, so every error is reported at once
+pub fn parse_file(input: &str) -> Result<Program, Vec<DashlangError>> {
+    if let Some(pos) = too_deep_nesting(input, MAX_NESTING) {
+        let message =
+            format!("Too deeply nested: brackets and prefix operators can't go more than {MAX_NESTING} levels deep");
+        return Err(vec![
+            DashlangError::new(&message, ErrorKind::Syntax).location(Location::new(pos, pos + 1))
+        ]);
+    }
+    let mut source = input.to_owned();
+    let mut errors: Vec<DashlangError> = vec![];
+    let mut ast = loop {
+        match DashlangParser::parse(Rule::file, &source) {
+            Ok(ast) => break ast,
+            Err(err) => {
+                let error = from_pest_error(err, &source);
+                let error_pos = error.location.map_or(0, |location| location.start);
+                // Skipping part of a statement can leave an error at the same position behind
+                if !errors.iter().any(|err| err.location == error.location) {
+                    errors.push(error);
+                }
+                if !skip_invalid_statement(&mut source, error_pos) {
+                    return Err(errors);
+                }
+            }
+        }
+    };
+    let location = Location::new(0, source.len());
+    let ast_program =
+        next_pair(&mut ast, location, "file").and_then(|ast_file| inner_pair(ast_file, "program"));
+    let ast_program = match ast_program {
+        Ok(ast_program) => ast_program,
+        Err(err) => {
+            errors.push(err);
+            return Err(errors);
+        }
+    };
+    let mut program = vec![];
+    for ast_statement in ast_program.into_inner() {
+        match parse_statement(ast_statement) {
+            Ok(statement) => program.push(statement),
+            Err(err) => errors.push(err),
+        }
+    }
+    errors.extend(validate_loop_control(&program));
+    if errors.is_empty() {
+        Ok(program)
+    } else {
+        errors.sort_by_key(|err| err.location.map(|location| location.start));
+        Err(errors)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroUsize;
+
+    use ast::{AssignmentExpr, Boolean, Expr, Int, Literal, Stmt};
+
     use super::*;
     #[test]
     fn test_parse_hello_world() {
         parse_file("println(true)").unwrap();
     }
     #[test]
+    fn test_parse_comments() {
+        assert_eq!(
+            parse_file("// leading\nage = /* inline */ 5 // trailing"),
+            Ok(vec![Stmt::Expr(Expr::Assignment(AssignmentExpr {
+                symbol: String::from("age"),
+                path: vec![],
+                operator: None,
+                value: Box::new(Expr::Literal(Literal::Int(Int {
+                    value: 5,
+                    location: Location::new(30, 31)
+                }))),
+                location: Location::new(11, 31),
+            }))])
+        );
+        assert_eq!(
+            parse_file("/* outer /* nested */ still outer */ true"),
+            Ok(vec![Stmt::Expr(Expr::Literal(Literal::Bool(Boolean {
+                value: true,
+                location: Location::new(37, 41)
+            })))])
+        );
+    }
+    #[test]
+    #[should_panic]
+    fn test_unterminated_block_comment() {
+        parse_file("/* never closed\nprintln(true)").unwrap();
+    }
+    #[test]
     #[should_panic]
     fn test_invalid_assignment() {
         parse_file("a = ").unwrap();
     }
+    fn syntax_error(message: &str, start: usize, end: usize) -> DashlangError {
+        DashlangError::new(message, ErrorKind::Syntax).location(Location::new(start, end))
+    }
+    #[test]
+    fn test_expected_expression_message() {
+        assert_eq!(
+            parse_file("a = "),
+            Err(vec![syntax_error(
+                "Expected an expression, found end of file",
+                4,
+                4
+            )])
+        );
+        assert_eq!(
+            parse_file("x = {a: 1, b 2}"),
+            Err(vec![syntax_error(
+                "Expected a map field, found `b`",
+                11,
+                12
+            )])
+        );
+    }
+    #[test]
+    fn test_collect_every_syntax_error() {
+        assert_eq!(
+            parse_file("a = 1 +\nb = 2\nc = )\nprintln(b)"),
+            Err(vec![
+                syntax_error("Unexpected `=`", 10, 11),
+                syntax_error("Expected an expression, found `)`", 18, 19),
+            ])
+        );
+    }
+    #[test]
+    fn test_collect_syntax_errors_inside_closure_body() {
+        assert_eq!(
+            parse_file("f = (x) {\n  y = x +\n  z = * 2\n  return y\n}\nw = [1,"),
+            Err(vec![
+                syntax_error("Unexpected `=`", 24, 25),
+                syntax_error("Expected an expression, found end of file", 50, 50),
+            ])
+        );
+    }
+    #[test]
+    fn test_collect_syntax_errors_around_braces_in_strings() {
+        assert_eq!(
+            parse_file("f = () {\n  s = r#\"\"}\"#\n  y = * 2\n  return y\n}\nw = )"),
+            Err(vec![
+                syntax_error("Expected an expression, found `*`", 29, 30),
+                syntax_error("Expected an expression, found `)`", 50, 51),
+            ])
+        );
+        assert_eq!(
+            parse_file("f = () {\n  s = \"\"\"\n\"}\"\"\"\n  y = * 2\n  return y\n}\nw = )"),
+            Err(vec![
+                syntax_error("Expected an expression, found `*`", 31, 32),
+                syntax_error("Expected an expression, found `)`", 52, 53),
+            ])
+        );
+    }
+    #[test]
+    fn test_stray_closing_brace() {
+        assert_eq!(
+            parse_file("}\na = 1\n}\nb = )"),
+            Err(vec![
+                syntax_error("Unexpected `}`", 0, 1),
+                syntax_error("Unexpected `}`", 8, 9),
+                syntax_error("Expected an expression, found `)`", 14, 15),
+            ])
+        );
+    }
+    #[test]
+    fn test_too_deep_nesting() {
+        let nested = |depth| format!("x = {}1{}", "[(".repeat(depth), ")]".repeat(depth));
+        assert!(parse_file(&nested(MAX_NESTING / 2)).is_ok());
+        let source = format!("// ((((\ns = \"[[[\"\n{}", nested(200));
+        let pos = source.find("[(").unwrap() + MAX_NESTING;
+        assert_eq!(
+            parse_file(&source),
+            Err(vec![syntax_error(
+                "Too deeply nested: brackets and prefix operators can't go more than 32 levels deep",
+                pos,
+                pos + 1
+            )])
+        );
+    }
+    #[test]
+    fn test_too_deep_prefix_operators() {
+        let source = format!("x = -{}1", "!".repeat(100));
+        assert_eq!(
+            parse_file(&source),
+            Err(vec![syntax_error(
+                "Too deeply nested: brackets and prefix operators can't go more than 32 levels deep",
+                36,
+                37
+            )])
+        );
+    }
+    #[test]
+    fn test_too_complex_expression() {
+        assert!(parse_file(&format!("x = 1{}", " + 1".repeat(127))).is_ok());
+        let source = format!("x = 1{}\ny = 2", " + 1".repeat(200));
+        assert_eq!(
+            parse_file(&source),
+            Err(vec![syntax_error(
+                "Expression too complex: it can't nest more than 128 operations",
+                4,
+                805
+            )])
+        );
+    }
+    #[test]
+    fn test_statement_starting_with_bracket_after_call() {
+        let program = parse_file("println(1)\n(a, b) = (1, 2)\nf(x)\n[c, d] = [3, 4]").unwrap();
+        assert_eq!(program.len(), 4);
+        assert!(matches!(
+            program[1],
+            Stmt::Expr(Expr::DestructuringAsignment(_))
+        ));
+        assert!(matches!(
+            program[3],
+            Stmt::Expr(Expr::DestructuringAsignment(_))
+        ));
+        // On the same line they still chain
+        assert_eq!(parse_file("f (1) [0]").unwrap().len(), 1);
+    }
+    #[test]
+    fn test_parse_large_generated_script() {
+        // Each nesting level used to multiply the work done by the parser. Bounding the number of
+        // rule calls by the input size catches that without depending on how fast the machine is
+        let mut nested = String::from("x");
+        for _ in 0..20 {
+            nested = format!("(1 + {nested})");
+        }
+        let mut closure = format!("return {nested}");
+        for level in 0..5 {
+            closure = format!("f{level} = () {{\nif true {{\n{closure}\n}}\nreturn 0\n}}");
+        }
+        let mut input = String::new();
+        for n in 0..2000 {
+            input.push_str(&format!(
+                "value{n} = [{n}, \"item ${{{n} * 2}}\", {{count: {n}}}]\n"
+            ));
+        }
+        input.push_str(&closure);
+
+        pest::set_call_limit(NonZeroUsize::new(100 * input.len()));
+        let within_limit = DashlangParser::parse(Rule::file, &input).is_ok();
+        pest::set_call_limit(None);
+        assert!(
+            within_limit,
+            "Parsing the generated script took too many rule calls"
+        );
+        let parsed = parse_file(&input);
+        assert_eq!(parsed.map(|program| program.len()), Ok(2001));
+    }
 }
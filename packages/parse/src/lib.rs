@@ -1,17 +1,21 @@
 use ast::Program;
-use errors::DashlangResult;
+use errors::DashlangError;
 use file::parse_file;
-use parser::{DashlangParser, Rule};
+use parser::Rule;
 
 mod body;
 mod expression;
 mod file;
 mod literal;
 mod parser;
+mod pattern;
 mod program;
+mod recovery;
 mod statement;
+mod syntax_error;
 mod utils;
+mod validation;
 
-pub fn parse(input: &str) -> DashlangResult<Program> {
+pub fn parse(input: &str) -> Result<Program, Vec<DashlangError>> {
     parse_file(input)
 }
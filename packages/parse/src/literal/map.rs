@@ -2,38 +2,29 @@ use std::collections::HashMap;
 
 use ast::Map;
 use errors::DashlangResult;
-use pest::Parser;
+use pest::iterators::Pair;
 
 use crate::{
     expression::parse_expression,
-    parser::{DashlangParser, Rule},
-    utils::get_pair_location,
+    parser::Rule,
+    utils::{get_pair_location, next_pair},
 };
 
-pub fn parse_map(input: &str, base_location: usize) -> DashlangResult<Map> {
-    let ast = DashlangParser::parse(Rule::map, input)
-        .expect("Could not parse map")
-        .next()
-        .expect("Could not parse map");
-    let (start, end) = get_pair_location(&ast);
+pub fn parse_map(ast: Pair<Rule>) -> DashlangResult<Map> {
+    let location = get_pair_location(&ast);
     let mut map_value = HashMap::new();
     for attribute in ast.into_inner() {
         let mut inner_attribute = attribute.into_inner();
-        let attr_ast_symbol = inner_attribute.next().expect("Could not parse attribute");
-
-        let attr_ast_value = inner_attribute
-            .next()
-            .expect("Could not get attribute value");
-        let (value_start, _) = get_pair_location(&attr_ast_value);
-        let parsed_attr_value =
-            parse_expression(attr_ast_value.as_str(), base_location + value_start)?;
-        let parsed_ast_symbol = attr_ast_symbol.as_str().to_owned();
-
-        map_value.insert(parsed_ast_symbol, parsed_attr_value);
+        let attr_ast_symbol = next_pair(&mut inner_attribute, location, "attribute")?;
+        let attr_ast_value = next_pair(&mut inner_attribute, location, "attribute value")?;
+        map_value.insert(
+            attr_ast_symbol.as_str().to_owned(),
+            parse_expression(attr_ast_value)?,
+        );
     }
     Ok(Map {
         value: map_value,
-        location: (start + base_location, end + base_location).into(),
+        location,
     })
 }
 
@@ -44,10 +35,11 @@ mod tests {
     use ast::{Expr, Int, Literal};
 
     use super::*;
+    use crate::utils::parse_rule;
     #[test]
     fn test_parse_map() {
         assert_eq!(
-            parse_map("{count: 0, count2: 1}", 0),
+            parse_map(parse_rule(Rule::map, "{count: 0, count2: 1}")),
             Ok(Map {
                 value: HashMap::from([
                     (
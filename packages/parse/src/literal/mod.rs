@@ -1,29 +1,21 @@
 mod map;
+pub mod string;
 
-use ast::{Atom, Boolean, Closure, Expr, Float, Int, Literal, Location, Str, Tuple, Vector};
+use ast::{Atom, Boolean, Closure, Expr, Float, Int, Literal, Null, Tuple, Vector};
 use errors::{DashlangError, DashlangResult, ErrorKind};
-use pest::Parser;
+use pest::iterators::Pair;
 
 use crate::body::parse_body;
 use crate::expression::parse_expression;
-use crate::parser::{DashlangParser, Rule};
-use crate::utils::get_pair_location;
+use crate::parser::Rule;
+use crate::pattern::parse_pattern;
+use crate::utils::{get_pair_location, inner_pair, next_pair, unexpected_pair};
 
-use self::map::parse_map;
+use self::{map::parse_map, string::parse_string};
 
-pub fn parse_literal(input: &str, base_location: usize) -> DashlangResult<Literal> {
-    let parsed = DashlangParser::parse(Rule::literal, input)
-        .expect("Could not parse value")
-        .next()
-        .expect("Could not parse value");
-    let (start, end) = get_pair_location(&parsed);
-    if parsed.as_rule() != Rule::literal {
-        return Err(
-            DashlangError::new("Expected rule to be a literal", ErrorKind::WrongArgs)
-                .location((start, end).into()),
-        );
-    }
-    let inner_value = parsed.into_inner().next().expect("Could not parse literal");
+pub fn parse_literal(ast: Pair<Rule>) -> DashlangResult<Literal> {
+    let location = get_pair_location(&ast);
+    let inner_value = inner_pair(ast, "literal")?;
     match inner_value.as_rule() {
         Rule::int => {
             let parsed: i64 = inner_value.as_str().parse().map_err(|_| {
@@ -31,173 +23,159 @@ pub fn parse_literal(input: &str, base_location: usize) -> DashlangResult<Litera
                     "Could not parse integer literal",
                     ErrorKind::InvalidOperation,
                 )
-                .location((start, end).into())
+                .location(location)
             })?;
             Ok(Literal::Int(Int {
                 value: parsed,
-                location: Location::new(start + base_location, end + base_location),
+                location,
             }))
         }
         Rule::float => {
             let parsed: f64 = inner_value.as_str().parse().map_err(|_| {
                 DashlangError::new("Could not parse float literal", ErrorKind::InvalidOperation)
-                    .location((start, end).into())
+                    .location(location)
             })?;
             Ok(Literal::Float(Float {
                 value: parsed,
-                location: Location::new(start + base_location, end + base_location),
+                location,
             }))
         }
         Rule::boolean => {
             let val = inner_value.as_str() == "true";
             Ok(Literal::Bool(Boolean {
                 value: val,
-                location: Location::new(start + base_location, end + base_location),
+                location,
             }))
         }
-        Rule::string => Ok(Literal::String(Str {
-            value: inner_value
-                .into_inner()
-                .next()
-                .ok_or(DashlangError::new(
-                    "Could not parse string",
-                    ErrorKind::InvalidOperation,
-                ))?
-                .as_str()
-                .to_owned(),
-            location: Location::new(start + base_location, end + base_location),
-        })),
+        Rule::null => Ok(Literal::Null(Null { location })),
+        Rule::string => Ok(Literal::String(parse_string(inner_value)?)),
         Rule::closure => {
             let mut inner_ast = inner_value.into_inner();
-            let params: Vec<String> = inner_ast
-                .next()
-                .expect("Could not get closure params")
+            let params = next_pair(&mut inner_ast, location, "closure params")?
                 .into_inner()
-                .map(|component| component.as_str().to_owned())
-                .collect();
-            let ast_body = inner_ast.next().expect("Could not get closure body");
-            let (body_start, _end) = get_pair_location(&ast_body);
-            let body = parse_body(ast_body.as_str(), body_start + base_location)?;
+                .map(parse_pattern)
+                .collect::<DashlangResult<_>>()?;
+            let ast_body = next_pair(&mut inner_ast, location, "closure body")?;
             Ok(Literal::Closure(Closure {
                 params,
-                body,
-                location: Location::new(start + base_location, end + base_location),
+                body: parse_body(ast_body)?,
+                location,
             }))
         }
         Rule::vector => {
-            let inner_ast = inner_value.into_inner();
-            let parsed_elements: DashlangResult<Vec<Expr>> = inner_ast
-                .map(|element| {
-                    let (element_start, _) = get_pair_location(&element);
-                    parse_expression(element.as_str(), element_start + base_location)
-                })
-                .collect();
+            let parsed_elements: DashlangResult<Vec<Expr>> =
+                inner_value.into_inner().map(parse_expression).collect();
             Ok(Literal::Vector(Vector {
                 value: parsed_elements?,
-                location: Location::new(start + base_location, end + base_location),
+                location,
             }))
         }
         Rule::tuple => {
-            let parsed_elements: DashlangResult<Vec<Expr>> = inner_value
-                .into_inner()
-                .map(|element| {
-                    let (element_start, _) = get_pair_location(&element);
-                    parse_expression(element.as_str(), element_start + base_location)
-                })
-                .collect();
-
+            let parsed_elements: DashlangResult<Vec<Expr>> =
+                inner_value.into_inner().map(parse_expression).collect();
             Ok(Literal::Tuple(Tuple {
                 value: parsed_elements?,
-                location: (start + base_location, end + base_location).into(),
+                location,
             }))
         }
-        Rule::map => Ok(Literal::Map(parse_map(
-            inner_value.as_str(),
-            start + base_location,
-        )?)),
+        Rule::map => Ok(Literal::Map(parse_map(inner_value)?)),
         Rule::atom => {
-            let (start, end) = get_pair_location(&inner_value);
-            let atom_value = inner_value.into_inner().next().ok_or(DashlangError::new(
-                "Could not parse atom",
-                ErrorKind::Unknown,
-            ))?;
-            let parsed_atom_value = atom_value.as_str().to_owned();
+            let location = get_pair_location(&inner_value);
+            let atom_value = inner_pair(inner_value, "atom value")?;
             Ok(Literal::Atom(Atom {
-                value: parsed_atom_value,
-                location: (start + base_location, end + base_location).into(),
+                value: atom_value.as_str().to_owned(),
+                location,
             }))
         }
-        _ => unreachable!(),
+        _ => Err(unexpected_pair(&inner_value)),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use ast::{Atom, Closure, Expr, Return, Stmt, Tuple};
+    use ast::{
+        Atom, Closure, Expr, Location, Pattern, Return, Stmt, Str, Symbol, Tuple, TuplePattern,
+    };
 
     use super::*;
+    use crate::utils::parse_rule;
     #[test]
     fn parse_value() {
         assert_eq!(
-            parse_literal("10", 0),
+            parse_literal(parse_rule(Rule::literal, "10")),
             Ok(Literal::Int(Int {
                 value: 10,
                 location: Location::new(0, 2)
             }))
         );
         assert_eq!(
-            parse_literal("-10", 0),
+            parse_literal(parse_rule(Rule::literal, "-10")),
             Ok(Literal::Int(Int {
                 value: -10,
                 location: Location::new(0, 3)
             }))
         );
         assert_eq!(
-            parse_literal("10.5", 0),
+            parse_literal(parse_rule(Rule::literal, "10.5")),
             Ok(Literal::Float(Float {
                 value: 10.5,
                 location: Location::new(0, 4)
             }))
         );
         assert_eq!(
-            parse_literal("-10.5", 0),
+            parse_literal(parse_rule(Rule::literal, "-10.5")),
             Ok(Literal::Float(Float {
                 value: -10.5,
                 location: Location::new(0, 5)
             }))
         );
         assert_eq!(
-            parse_literal("true", 0),
+            parse_literal(parse_rule(Rule::literal, "true")),
             Ok(Literal::Bool(Boolean {
                 value: true,
                 location: Location::new(0, 4)
             }))
         );
         assert_eq!(
-            parse_literal("false", 0),
+            parse_literal(parse_rule(Rule::literal, "false")),
             Ok(Literal::Bool(Boolean {
                 value: false,
                 location: Location::new(0, 5)
             }))
         );
         assert_eq!(
-            parse_literal(r#""apple""#, 0),
+            parse_literal(parse_rule(Rule::literal, "null")),
+            Ok(Literal::Null(Null {
+                location: Location::new(0, 4)
+            }))
+        );
+        assert_eq!(
+            parse_literal(parse_rule(Rule::literal, r#""apple""#)),
             Ok(Literal::String(Str {
                 value: String::from("apple"),
                 location: Location::new(0, 7)
             }))
         );
         assert_eq!(
-            parse_literal(r#""green apple""#, 0),
+            parse_literal(parse_rule(Rule::literal, r#""green apple""#)),
             Ok(Literal::String(Str {
                 value: "green apple".to_owned(),
                 location: Location::new(0, 13)
             }))
         );
         assert_eq!(
-            parse_literal("(name, age) {return true}", 0),
+            parse_literal(parse_rule(Rule::literal, "(name, age) {return true}")),
             Ok(Literal::Closure(Closure {
-                params: vec![String::from("name"), String::from("age")],
+                params: vec![
+                    Pattern::Binding(Symbol {
+                        value: String::from("name"),
+                        location: Location::new(1, 5)
+                    }),
+                    Pattern::Binding(Symbol {
+                        value: String::from("age"),
+                        location: Location::new(7, 10)
+                    }),
+                ],
                 body: vec![Stmt::Return(Return {
                     value: Expr::Literal(Literal::Bool(Boolean {
                         value: true,
@@ -210,9 +188,35 @@ mod tests {
         );
     }
     #[test]
+    fn test_parse_closure_with_patterns() {
+        let parsed = parse_literal(parse_rule(Rule::literal, "((x, y), _) {return x}"));
+        let Ok(Literal::Closure(closure)) = parsed else {
+            panic!("Expected a closure, got {parsed:?}");
+        };
+        assert_eq!(
+            closure.params,
+            vec![
+                Pattern::Tuple(TuplePattern {
+                    items: vec![
+                        Pattern::Binding(Symbol {
+                            value: String::from("x"),
+                            location: Location::new(2, 3)
+                        }),
+                        Pattern::Binding(Symbol {
+                            value: String::from("y"),
+                            location: Location::new(5, 6)
+                        }),
+                    ],
+                    location: Location::new(1, 7)
+                }),
+                Pattern::Wildcard(Location::new(9, 10)),
+            ]
+        );
+    }
+    #[test]
     fn test_parse_vector() {
         assert_eq!(
-            parse_literal("[1, 8, 7]", 0),
+            parse_literal(parse_rule(Rule::literal, "[1, 8, 7]")),
             Ok(Literal::Vector(Vector {
                 value: vec![
                     Expr::Literal(Literal::Int(Int {
@@ -233,9 +237,28 @@ mod tests {
         );
     }
     #[test]
+    fn test_parse_literal_with_comments() {
+        assert_eq!(
+            parse_literal(parse_rule(Rule::literal, "[1, /* two */ 2 // three\n]")),
+            Ok(Literal::Vector(Vector {
+                value: vec![
+                    Expr::Literal(Literal::Int(Int {
+                        value: 1,
+                        location: Location::new(1, 2)
+                    })),
+                    Expr::Literal(Literal::Int(Int {
+                        value: 2,
+                        location: Location::new(14, 15)
+                    })),
+                ],
+                location: Location::new(0, 26)
+            }))
+        );
+    }
+    #[test]
     fn test_parse_tuple() {
         assert_eq!(
-            parse_literal("(1, 4)", 0),
+            parse_literal(parse_rule(Rule::literal, "(1, 4)")),
             Ok(Literal::Tuple(Tuple {
                 value: vec![
                     Expr::Literal(Literal::Int(Int {
@@ -254,7 +277,7 @@ mod tests {
     #[test]
     fn test_parse_atom() {
         assert_eq!(
-            parse_literal(":ok", 0),
+            parse_literal(parse_rule(Rule::literal, ":ok")),
             Ok(Literal::Atom(Atom {
                 value: "ok".to_string(),
                 location: (0, 3).into()
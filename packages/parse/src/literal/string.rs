@@ -0,0 +1,211 @@
+use errors::{DashlangError, DashlangResult, ErrorKind};
+use pest::iterators::Pair;
+
+use ast::{Location, Str};
+
+use crate::{
+    parser::Rule,
+    utils::{get_pair_location, inner_pair},
+};
+
+pub fn parse_string(ast: Pair<Rule>) -> DashlangResult<Str> {
+    let location = get_pair_location(&ast);
+    let ast_string = inner_pair(ast, "string kind")?;
+    let rule = ast_string.as_rule();
+    let kind_location = get_pair_location(&ast_string);
+    let ast_text = inner_pair(ast_string, "string content")?;
+    let text_start = get_pair_location(&ast_text).start;
+    let value = match rule {
+        Rule::raw_string => ast_text.as_str().to_owned(),
+        Rule::multiline_string => {
+            // A line break right after the opening quotes is not part of the string
+            let text = ast_text.as_str();
+            let skipped = if text.starts_with("\r\n") {
+                2
+            } else if text.starts_with('\n') {
+                1
+            } else {
+                0
+            };
+            unescape(&text[skipped..], text_start + skipped)?
+        }
+        Rule::regular_string => unescape(ast_text.as_str(), text_start)?,
+        _ => {
+            return Err(
+                DashlangError::new(&format!("Unexpected {rule:?}"), ErrorKind::Unknown)
+                    .location(kind_location),
+            )
+        }
+    };
+    Ok(Str { value, location })
+}
+
+/// `base_location` is where `input` starts in the source, so errors point at the offending escape
+pub fn unescape(input: &str, base_location: usize) -> DashlangResult<String> {
+    let mut result = String::with_capacity(input.len());
+    let mut chars = input.char_indices().peekable();
+    while let Some((escape_start, ch)) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+        let invalid_escape = |escape_end: usize, message: &str| {
+            DashlangError::new(
+                &format!("{message} '{}'", &input[escape_start..escape_end]),
+                ErrorKind::Syntax,
+            )
+            .location(Location::new(
+                escape_start + base_location,
+                escape_end + base_location,
+            ))
+        };
+        match chars.next() {
+            Some((_, '"')) => result.push('"'),
+            Some((_, '\\')) => result.push('\\'),
+            Some((_, 'n')) => result.push('\n'),
+            Some((_, 't')) => result.push('\t'),
+            Some((_, 'r')) => result.push('\r'),
+            Some((_, '0')) => result.push('\0'),
+            Some((_, '$')) => result.push('$'),
+            Some((u_index, 'u')) => {
+                let mut escape_end = u_index + 1;
+                if chars.next_if(|(_, ch)| *ch == '{').is_none() {
+                    return Err(invalid_escape(
+                        escape_end,
+                        "Expected '{' in unicode escape sequence",
+                    ));
+                }
+                escape_end += 1;
+                let mut digits = String::new();
+                let mut closed = false;
+                while let Some((index, ch)) =
+                    chars.next_if(|(_, ch)| ch.is_ascii_hexdigit() || *ch == '}')
+                {
+                    escape_end = index + ch.len_utf8();
+                    if ch == '}' {
+                        closed = true;
+                        break;
+                    }
+                    digits.push(ch);
+                }
+                if !closed {
+                    if let Some((index, ch)) = chars.peek() {
+                        escape_end = index + ch.len_utf8();
+                    }
+                    return Err(invalid_escape(
+                        escape_end,
+                        "Invalid unicode escape sequence",
+                    ));
+                }
+                if digits.is_empty() || digits.len() > 6 {
+                    return Err(invalid_escape(
+                        escape_end,
+                        "Unicode escape sequences must have between 1 and 6 hex digits",
+                    ));
+                }
+                let code_point = u32::from_str_radix(&digits, 16)
+                    .map_err(|_| invalid_escape(escape_end, "Invalid unicode escape sequence"))?;
+                let decoded = char::from_u32(code_point).ok_or_else(|| {
+                    invalid_escape(escape_end, "Invalid unicode code point in escape sequence")
+                })?;
+                result.push(decoded);
+            }
+            Some((index, other)) => {
+                return Err(invalid_escape(
+                    index + other.len_utf8(),
+                    "Invalid escape sequence",
+                ))
+            }
+            None => return Err(invalid_escape(input.len(), "Invalid escape sequence")),
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_rule;
+
+    #[test]
+    fn test_escape_sequences() {
+        assert_eq!(
+            parse_string(parse_rule(Rule::string, r#""say \"hi\"\n\tand \\ bye""#)),
+            Ok(Str {
+                value: "say \"hi\"\n\tand \\ bye".to_owned(),
+                location: Location::new(0, 26)
+            })
+        );
+        assert_eq!(
+            parse_string(parse_rule(Rule::string, r#""\u{48}\u{e9}\u{1F600}""#)),
+            Ok(Str {
+                value: "Hé😀".to_owned(),
+                location: Location::new(0, 23)
+            })
+        );
+    }
+    #[test]
+    fn test_raw_string() {
+        assert_eq!(
+            parse_string(parse_rule(Rule::string, r#"r"C:\path\n""#)),
+            Ok(Str {
+                value: r"C:\path\n".to_owned(),
+                location: Location::new(0, 12)
+            })
+        );
+        assert_eq!(
+            parse_string(parse_rule(Rule::string, r###"r#"a "quoted" \ word"#"###)),
+            Ok(Str {
+                value: r#"a "quoted" \ word"#.to_owned(),
+                location: Location::new(0, 22)
+            })
+        );
+    }
+    #[test]
+    fn test_multiline_string() {
+        assert_eq!(
+            parse_string(parse_rule(
+                Rule::string,
+                "\"\"\"\nfirst \"line\"\n\tsecond\\tline\"\"\""
+            )),
+            Ok(Str {
+                value: "first \"line\"\n\tsecond\tline".to_owned(),
+                location: Location::new(0, 33)
+            })
+        );
+    }
+    #[test]
+    fn test_invalid_escapes() {
+        assert_eq!(
+            parse_string(parse_rule(Rule::string, r#""abc\qdef""#)),
+            Err(
+                DashlangError::new("Invalid escape sequence '\\q'", ErrorKind::Syntax)
+                    .location(Location::new(4, 6))
+            )
+        );
+        assert_eq!(
+            parse_string(parse_rule(Rule::string, r#""\u{110000}""#)),
+            Err(DashlangError::new(
+                "Invalid unicode code point in escape sequence '\\u{110000}'",
+                ErrorKind::Syntax
+            )
+            .location(Location::new(1, 11)))
+        );
+        assert_eq!(
+            parse_string(parse_rule(Rule::string, r#""x\u{12g}""#)),
+            Err(DashlangError::new(
+                "Invalid unicode escape sequence '\\u{12g'",
+                ErrorKind::Syntax
+            )
+            .location(Location::new(2, 8)))
+        );
+        assert_eq!(
+            parse_string(parse_rule(Rule::string, r#""\u41""#)),
+            Err(DashlangError::new(
+                "Expected '{' in unicode escape sequence '\\u'",
+                ErrorKind::Syntax
+            )
+            .location(Location::new(1, 3)))
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use ast::{MapPattern, Pattern, RestPattern, TuplePattern, VectorPattern};
+use errors::DashlangResult;
+use pest::iterators::Pair;
+
+use crate::{
+    expression::parse_symbol,
+    literal::parse_literal,
+    parser::Rule,
+    utils::{get_pair_location, inner_pair, next_pair, unexpected_pair},
+};
+
+pub fn parse_pattern(ast: Pair<Rule>) -> DashlangResult<Pattern> {
+    let inner = inner_pair(ast, "pattern")?;
+    let location = get_pair_location(&inner);
+    match inner.as_rule() {
+        Rule::wildcard_pattern => Ok(Pattern::Wildcard(location)),
+        Rule::symbol => Ok(Pattern::Binding(parse_symbol(inner))),
+        // Has the same shape as a literal, so it's parsed the same way
+        Rule::literal_pattern => Ok(Pattern::Literal(parse_literal(inner)?)),
+        Rule::tuple_pattern => Ok(Pattern::Tuple(TuplePattern {
+            items: inner
+                .into_inner()
+                .map(parse_pattern)
+                .collect::<DashlangResult<_>>()?,
+            location,
+        })),
+        Rule::vector_pattern => {
+            let mut items = vec![];
+            let mut rest = None;
+            for item in inner.into_inner() {
+                match item.as_rule() {
+                    Rule::rest_pattern => {
+                        rest = Some(RestPattern {
+                            location: get_pair_location(&item),
+                            symbol: item.into_inner().next().map(parse_symbol),
+                        })
+                    }
+                    _ => items.push(parse_pattern(item)?),
+                }
+            }
+            Ok(Pattern::Vector(VectorPattern {
+                items,
+                rest,
+                location,
+            }))
+        }
+        Rule::map_pattern => {
+            let mut fields = vec![];
+            for field in inner.into_inner() {
+                let field_location = get_pair_location(&field);
+                let mut field_inner = field.into_inner();
+                let key = parse_symbol(next_pair(&mut field_inner, field_location, "map key")?);
+                let pattern = match field_inner.next() {
+                    Some(pattern) => parse_pattern(pattern)?,
+                    None => Pattern::Binding(key.clone()),
+                };
+                fields.push((key.value, pattern));
+            }
+            Ok(Pattern::Map(MapPattern { fields, location }))
+        }
+        _ => Err(unexpected_pair(&inner)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_rule;
+    use ast::{Atom, Int, Literal, Location, Symbol};
+
+    fn binding(value: &str, start: usize, end: usize) -> Pattern {
+        Pattern::Binding(Symbol {
+            value: String::from(value),
+            location: Location::new(start, end),
+        })
+    }
+
+    #[test]
+    fn test_parse_tuple_pattern() {
+        assert_eq!(
+            parse_pattern(parse_rule(Rule::pattern, "(:ok, value, _)")),
+            Ok(Pattern::Tuple(TuplePattern {
+                items: vec![
+                    Pattern::Literal(Literal::Atom(Atom {
+                        value: String::from("ok"),
+                        location: Location::new(1, 4)
+                    })),
+                    binding("value", 6, 11),
+                    Pattern::Wildcard(Location::new(13, 14)),
+                ],
+                location: Location::new(0, 15),
+            }))
+        );
+    }
+    #[test]
+    fn test_parse_vector_pattern_with_rest() {
+        assert_eq!(
+            parse_pattern(parse_rule(Rule::pattern, "[-1, head, ...tail]")),
+            Ok(Pattern::Vector(VectorPattern {
+                items: vec![
+                    Pattern::Literal(Literal::Int(Int {
+                        value: -1,
+                        location: Location::new(1, 3)
+                    })),
+                    binding("head", 5, 9),
+                ],
+                rest: Some(RestPattern {
+                    symbol: Some(Symbol {
+                        value: String::from("tail"),
+                        location: Location::new(14, 18)
+                    }),
+                    location: Location::new(11, 18),
+                }),
+                location: Location::new(0, 19),
+            }))
+        );
+        assert_eq!(
+            parse_pattern(parse_rule(Rule::pattern, "[...]")),
+            Ok(Pattern::Vector(VectorPattern {
+                items: vec![],
+                rest: Some(RestPattern {
+                    symbol: None,
+                    location: Location::new(1, 4),
+                }),
+                location: Location::new(0, 5),
+            }))
+        );
+    }
+    #[test]
+    fn test_parse_map_pattern() {
+        assert_eq!(
+            parse_pattern(parse_rule(Rule::pattern, "{name, age: _age}")),
+            Ok(Pattern::Map(MapPattern {
+                fields: vec![
+                    (String::from("name"), binding("name", 1, 5)),
+                    (String::from("age"), binding("_age", 12, 16)),
+                ],
+                location: Location::new(0, 17),
+            }))
+        );
+    }
+}
@@ -1,24 +1,11 @@
 use ast::Program;
 use errors::DashlangResult;
-use pest::Parser;
+use pest::iterators::Pair;
 
-use crate::{
-    parser::{DashlangParser, Rule},
-    statement::parse_statement,
-    utils::get_pair_location,
-};
+use crate::{parser::Rule, statement::parse_statement};
 
-pub fn parse_program(input: &str) -> DashlangResult<Program> {
-    let mut program: Program = vec![];
-    let ast = DashlangParser::parse(Rule::program, input)
-        .expect("Could not parse program")
-        .next()
-        .expect("Could not parse program");
-    for stmt in ast.into_inner() {
-        let (start, _end) = get_pair_location(&stmt);
-        program.push(parse_statement(stmt.as_str(), start)?);
-    }
-    Ok(program)
+pub fn parse_program(ast: Pair<Rule>) -> DashlangResult<Program> {
+    ast.into_inner().map(parse_statement).collect()
 }
 
 #[cfg(test)]
@@ -27,22 +14,27 @@ mod tests {
     use ast::{AssignmentExpr, Expr, Int, Literal, Location, Stmt};
 
     use super::*;
+    use crate::utils::parse_rule;
 
     #[test]
     fn test_parse_program() {
         assert_eq!(
-            parse_program("age = 5 count = 1"),
+            parse_program(parse_rule(Rule::program, "age = 5 count = 1")),
             Ok(vec![
                 Stmt::Expr(Expr::Assignment(AssignmentExpr {
                     symbol: String::from("age"),
+                    path: vec![],
+                    operator: None,
                     value: Box::new(Expr::Literal(Literal::Int(Int {
                         value: 5,
                         location: Location::new(6, 7)
                     }))),
-                    location: Location::new(0, 8),
+                    location: Location::new(0, 7),
                 })),
                 Stmt::Expr(Expr::Assignment(AssignmentExpr {
                     symbol: String::from("count"),
+                    path: vec![],
+                    operator: None,
                     value: Box::new(Expr::Literal(Literal::Int(Int {
                         value: 1,
                         location: Location::new(16, 17)
@@ -0,0 +1,203 @@
+use pest::Parser;
+
+use crate::parser::{DashlangParser, Rule};
+
+/// `r` only starts a raw string when it isn't the end of a symbol
+fn string_len(rest: &str, after_symbol: bool) -> Option<usize> {
+    let bytes = rest.as_bytes();
+    let escaped_until = |start: usize, quote: &[u8]| {
+        let mut pos = start;
+        while pos < bytes.len() && !bytes[pos..].starts_with(quote) {
+            pos += if bytes[pos] == b'\\' { 2 } else { 1 };
+        }
+        (pos + quote.len()).min(bytes.len())
+    };
+    if rest.starts_with("\"\"\"") {
+        return Some(escaped_until(3, b"\"\"\""));
+    }
+    if rest.starts_with('"') {
+        return Some(escaped_until(1, b"\""));
+    }
+    let hashes = rest.strip_prefix('r').filter(|_| !after_symbol)?;
+    let hashes = hashes.len() - hashes.trim_start_matches('#').len();
+    let text_start = 1 + hashes + 1;
+    if bytes.get(text_start - 1) != Some(&b'"') {
+        return None;
+    }
+    let closing = format!("\"{}", "#".repeat(hashes));
+    Some(
+        rest[text_start..]
+            .find(&closing)
+            .map_or(rest.len(), |end| text_start + end + closing.len()),
+    )
+}
+
+/// Length of the line or block comment at the start of `rest`, if there is one
+pub fn comment_len(rest: &str) -> Option<usize> {
+    if rest.starts_with("//") {
+        return Some(rest.find('\n').unwrap_or(rest.len()));
+    }
+    if !rest.starts_with("/*") {
+        return None;
+    }
+    let bytes = rest.as_bytes();
+    let mut depth = 0;
+    let mut pos = 0;
+    while pos < rest.len() {
+        if bytes[pos..].starts_with(b"/*") {
+            depth += 1;
+            pos += 2;
+        } else if bytes[pos..].starts_with(b"*/") {
+            depth -= 1;
+            pos += 2;
+            if depth == 0 {
+                break;
+            }
+        } else {
+            pos += 1;
+        }
+    }
+    Some(pos)
+}
+
+/// Walks `source` from `start`, calling `visit` with the position of every character outside of
+/// strings and comments until it returns `false`. Returns where the walk stopped
+fn scan(source: &str, start: usize, mut visit: impl FnMut(usize, char) -> bool) -> usize {
+    let mut pos = start;
+    while pos < source.len() {
+        let rest = &source[pos..];
+        if let Some(len) = comment_len(rest) {
+            pos += len;
+            continue;
+        }
+        let after_symbol = source[..pos]
+            .chars()
+            .next_back()
+            .is_some_and(|ch| ch.is_alphanumeric() || ch == '_');
+        if let Some(len) = string_len(rest, after_symbol) {
+            pos += len;
+            continue;
+        }
+        let Some(ch) = rest.chars().next() else {
+            break;
+        };
+        if !visit(pos, ch) {
+            return pos;
+        }
+        pos += ch.len_utf8();
+    }
+    source.len().min(pos)
+}
+
+fn enclosing_block_start(source: &str, pos: usize) -> usize {
+    let mut open_blocks = vec![];
+    scan(source, 0, |index, ch| {
+        if index >= pos {
+            return false;
+        }
+        match ch {
+            '{' => open_blocks.push(index + 1),
+            '}' => {
+                open_blocks.pop();
+            }
+            _ => {}
+        }
+        true
+    });
+    open_blocks.pop().unwrap_or(0)
+}
+
+fn invalid_statement_start(source: &str, block_start: usize) -> usize {
+    let skip_whitespace = |pos| scan(source, pos, |_, ch| ch.is_whitespace());
+    let program_start = skip_whitespace(block_start);
+    let valid_len = DashlangParser::parse(Rule::program, &source[program_start..])
+        .ok()
+        .and_then(|mut pairs| pairs.next())
+        .map_or(0, |program| program.as_span().end());
+    skip_whitespace(program_start + valid_len)
+}
+
+/// Where the statement starting at `start` ends: at the end of its line, unless a bracket is
+/// still open, or right before a `}` that closes the enclosing block
+fn statement_end(source: &str, start: usize) -> usize {
+    let mut depth = 0;
+    scan(source, start, |_, ch| match ch {
+        '(' | '[' | '{' => {
+            depth += 1;
+            true
+        }
+        ')' | ']' | '}' if depth == 0 => false,
+        ')' | ']' | '}' => {
+            depth -= 1;
+            true
+        }
+        '\n' => depth > 0,
+        _ => true,
+    })
+}
+
+/// The parser recurses on every level, so deep nesting would run out of stack. A run of prefix
+/// operators nests like brackets do, until the operand after it ends
+pub fn too_deep_nesting(source: &str, max_depth: usize) -> Option<usize> {
+    // Prefix operators right before each bracket that is still open
+    let mut open = vec![];
+    let mut depth = 0;
+    let mut prefix_run = 0;
+    let stop = scan(source, 0, |_, ch| {
+        match ch {
+            '!' | '~' | '-' => prefix_run += 1,
+            '(' | '[' | '{' => {
+                depth += prefix_run + 1;
+                open.push(prefix_run);
+                prefix_run = 0;
+            }
+            ')' | ']' | '}' => {
+                depth -= open.pop().map_or(0, |run| run + 1);
+                prefix_run = 0;
+            }
+            ch if ch.is_whitespace() => {}
+            _ => prefix_run = 0,
+        }
+        depth + prefix_run <= max_depth
+    });
+    (stop < source.len()).then_some(stop)
+}
+
+/// Replaces `source[start..end]` with spaces, keeping line breaks and byte offsets intact.
+/// Returns whether anything but whitespace was removed
+fn blank(source: &mut String, start: usize, end: usize) -> bool {
+    let removed = &source[start..end];
+    if removed.chars().all(char::is_whitespace) {
+        return false;
+    }
+    let replacement: String = removed
+        .chars()
+        .map(|ch| {
+            if ch == '\n' {
+                "\n".to_owned()
+            } else {
+                " ".repeat(ch.len_utf8())
+            }
+        })
+        .collect();
+    source.replace_range(start..end, &replacement);
+    true
+}
+
+fn skip_statement_in_block(source: &mut String, block_start: usize) -> bool {
+    let start = invalid_statement_start(source, block_start);
+    let mut end = statement_end(source, start);
+    if end == start {
+        // Nothing parseable starts here, like a stray `)`, so at least that character goes
+        end += source[start..].chars().next().map_or(0, char::len_utf8);
+    }
+    blank(source, start, end)
+}
+
+/// Blanks out the statement that made parsing fail at `error_pos`, trying the innermost block first
+/// and then the whole top level statement. Returns `false` when nothing could be skipped
+pub fn skip_invalid_statement(source: &mut String, error_pos: usize) -> bool {
+    let block_start = enclosing_block_start(source, error_pos);
+    skip_statement_in_block(source, block_start)
+        || (block_start != 0 && skip_statement_in_block(source, 0))
+}
@@ -0,0 +1,85 @@
+use ast::ForIn;
+use errors::DashlangResult;
+use pest::iterators::Pair;
+
+use crate::{
+    body::parse_body,
+    expression::parse_condition,
+    parser::Rule,
+    pattern::parse_pattern,
+    utils::{get_pair_location, next_pair},
+};
+
+use super::loop_control::parse_loop_label;
+
+pub fn parse_for_in_stmt(ast: Pair<Rule>) -> DashlangResult<ForIn> {
+    let location = get_pair_location(&ast);
+    let mut inner_ast = ast.into_inner();
+    let label = parse_loop_label(&mut inner_ast);
+    let pattern = parse_pattern(next_pair(
+        &mut inner_ast,
+        location,
+        "for in statement pattern",
+    )?)?;
+    // Skips the `in` keyword
+    next_pair(&mut inner_ast, location, "`in` from for in statement")?;
+    let iterable = next_pair(&mut inner_ast, location, "for in statement iterable")?;
+    let body = next_pair(&mut inner_ast, location, "for in statement body")?;
+    Ok(ForIn {
+        label,
+        pattern,
+        iterable: parse_condition(iterable)?,
+        body: parse_body(body)?,
+        location,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_rule;
+    use ast::{Expr, Location, Pattern, Symbol, TuplePattern};
+
+    fn symbol(value: &str, start: usize, end: usize) -> Symbol {
+        Symbol {
+            value: String::from(value),
+            location: Location::new(start, end),
+        }
+    }
+
+    #[test]
+    fn test_for_in_stmt() {
+        assert_eq!(
+            parse_for_in_stmt(parse_rule(Rule::for_in_stmt, "for item in items {}")),
+            Ok(ForIn {
+                label: None,
+                pattern: Pattern::Binding(symbol("item", 4, 8)),
+                iterable: Expr::Symbol(symbol("items", 12, 17)),
+                body: vec![],
+                location: Location::new(0, 20),
+            })
+        );
+    }
+    #[test]
+    fn test_for_in_stmt_with_tuple_target() {
+        assert_eq!(
+            parse_for_in_stmt(parse_rule(
+                Rule::for_in_stmt,
+                "'outer: for (key, value) in map {}"
+            )),
+            Ok(ForIn {
+                label: Some(String::from("outer")),
+                pattern: Pattern::Tuple(TuplePattern {
+                    items: vec![
+                        Pattern::Binding(symbol("key", 13, 16)),
+                        Pattern::Binding(symbol("value", 18, 23)),
+                    ],
+                    location: Location::new(12, 24),
+                }),
+                iterable: Expr::Symbol(symbol("map", 28, 31)),
+                body: vec![],
+                location: Location::new(0, 34),
+            })
+        );
+    }
+}
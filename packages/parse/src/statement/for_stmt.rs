@@ -1,63 +1,64 @@
-use ast::{For, Location};
+use ast::For;
 use errors::DashlangResult;
-use pest::Parser;
+use pest::iterators::Pair;
 
 use crate::{
     body::parse_body,
     expression::parse_expression,
-    parser::{DashlangParser, Rule},
-    utils::get_pair_location,
+    parser::Rule,
+    utils::{get_pair_location, next_pair},
 };
 
-use super::parse_statement;
+use super::{loop_control::parse_loop_label, parse_statement};
 
-pub fn parse_for_stmt(input: &str, base_location: usize) -> DashlangResult<For> {
-    let ast = DashlangParser::parse(Rule::for_stmt, input)
-        .expect("Could not parse for statement")
-        .next()
-        .expect("Could not get for statement");
-    let (start, end) = get_pair_location(&ast);
+pub fn parse_for_stmt(ast: Pair<Rule>) -> DashlangResult<For> {
+    let location = get_pair_location(&ast);
     let mut inner_ast = ast.into_inner();
-    let init_stmt = inner_ast
-        .next()
-        .expect("Could not get init statement from for statement");
-    let (init_start, _) = get_pair_location(&init_stmt);
-    let cond_expr = inner_ast
-        .next()
-        .expect("Could not get condition expr from for statement");
-    let (cond_start, _) = get_pair_location(&cond_expr);
-    let iteration_statement = inner_ast
-        .next()
-        .expect("Could not get iteration statement from for statement");
-    let (iteration_start, _) = get_pair_location(&iteration_statement);
-    let for_body = inner_ast.next().expect("Could not get for statement body");
-    let (body_start, _) = get_pair_location(&for_body);
+    let label = parse_loop_label(&mut inner_ast);
+    let init_stmt = next_pair(
+        &mut inner_ast,
+        location,
+        "init statement from for statement",
+    )?;
+    let cond_expr = next_pair(
+        &mut inner_ast,
+        location,
+        "condition expr from for statement",
+    )?;
+    let iteration_statement = next_pair(
+        &mut inner_ast,
+        location,
+        "iteration statement from for statement",
+    )?;
+    let for_body = next_pair(&mut inner_ast, location, "for statement body")?;
 
     Ok(For {
-        cond: parse_expression(cond_expr.as_str(), cond_start + base_location)?,
-        body: parse_body(for_body.as_str(), body_start + base_location)?,
-        init: parse_statement(init_stmt.as_str(), init_start + base_location)?,
-        iteration: parse_statement(
-            iteration_statement.as_str(),
-            iteration_start + base_location,
-        )?,
-        location: Location::new(start, end),
+        label,
+        cond: parse_expression(cond_expr)?,
+        body: parse_body(for_body)?,
+        init: parse_statement(init_stmt)?,
+        iteration: parse_statement(iteration_statement)?,
+        location,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::parse_rule;
     use ast::{
         AssignmentExpr, BinaryExpr, BinaryOperator, Expr, Int, Literal, Location, Stmt, Symbol,
     };
     #[test]
     fn test_for_stmt() {
         assert_eq!(
-            parse_for_stmt("for n = 1; n < 10; n += 1 {}", 0),
+            parse_for_stmt(parse_rule(Rule::for_stmt, "for n = 1; n < 10; n += 1 {}")),
             Ok(For {
+                label: None,
                 init: Stmt::Expr(Expr::Assignment(AssignmentExpr {
                     symbol: String::from("n"),
+                    path: vec![],
+                    operator: None,
                     value: Box::new(Expr::Literal(Literal::Int(Int {
                         value: 1,
                         location: Location::new(8, 9)
@@ -79,19 +80,13 @@ mod tests {
                 body: vec![],
                 iteration: Stmt::Expr(Expr::Assignment(AssignmentExpr {
                     symbol: String::from("n"),
-                    value: Box::new(Expr::BinaryExpr(Box::new(BinaryExpr {
-                        left: Expr::Symbol(Symbol {
-                            value: String::from("n"),
-                            location: Location::new(19, 20)
-                        }),
-                        right: Expr::Literal(Literal::Int(Int {
-                            value: 1,
-                            location: Location::new(24, 25)
-                        })),
-                        operator: BinaryOperator::Add,
-                        location: Location::new(19, 26),
+                    path: vec![],
+                    operator: Some(BinaryOperator::Add),
+                    value: Box::new(Expr::Literal(Literal::Int(Int {
+                        value: 1,
+                        location: Location::new(24, 25)
                     }))),
-                    location: Location::new(19, 26),
+                    location: Location::new(19, 25),
                 })),
                 location: Location::new(0, 28),
             })
@@ -1,116 +1,56 @@
-use ast::{If, Location, Program, Stmt};
+use ast::{If, Program, Stmt};
 use errors::DashlangResult;
-use pest::Parser;
+use pest::iterators::Pair;
 
 use crate::{
     body::parse_body,
-    expression::parse_expression,
-    parser::{DashlangParser, Rule},
-    utils::get_pair_location,
+    expression::parse_condition,
+    parser::Rule,
+    utils::{get_pair_location, inner_pair, next_pair, unexpected_pair},
 };
 
-pub fn parse_if_stmt(input: &str, base_location: usize) -> DashlangResult<If> {
-    let ast = DashlangParser::parse(Rule::if_stmt, input)
-        .expect("Could not parse if statement")
-        .next()
-        .expect("Could not parse if statement");
-    let (start, end) = get_pair_location(&ast);
+/// Also parses `if_expression` and `else_if_stmt`, which only differ by where they're used
+pub fn parse_if_stmt(ast: Pair<Rule>) -> DashlangResult<If> {
+    let location = get_pair_location(&ast);
     let mut inner_ast = ast.into_inner();
-    let ast_cond = inner_ast.next().expect("Could not get condition");
-    let (cond_start, _) = get_pair_location(&ast_cond);
-    let parsed_cond = parse_expression(ast_cond.as_str(), cond_start + base_location)?;
-
-    let ast_body = inner_ast.next().expect("Could not get scope");
-    let (body_start, _) = get_pair_location(&ast_body);
-    let parsed_body = parse_body(ast_body.as_str(), body_start + base_location)?;
-
-    let ast_else = match inner_ast.next() {
-        Some(pair) => {
-            let (else_start, _) = get_pair_location(&pair);
-            match pair.as_rule() {
-                Rule::else_stmt => {
-                    Some(parse_else_stmt(pair.as_str(), else_start + base_location)?)
-                }
-                Rule::else_if_stmt => Some(vec![Stmt::If(parse_else_if_stmt(
-                    pair.as_str(),
-                    else_start + base_location,
-                )?)]),
-                _ => unreachable!(),
-            }
-        }
+    let ast_cond = next_pair(&mut inner_ast, location, "condition")?;
+    let ast_body = next_pair(&mut inner_ast, location, "scope")?;
+    let else_block = match inner_ast.next() {
+        Some(pair) => Some(parse_else(pair)?),
         None => None,
     };
     Ok(If {
-        cond: parsed_cond,
-        body: parsed_body,
-        else_block: ast_else,
-        location: Location::new(start + base_location, end + base_location),
+        cond: parse_condition(ast_cond)?,
+        body: parse_body(ast_body)?,
+        else_block,
+        location,
     })
 }
-fn parse_else_stmt(input: &str, base_location: usize) -> DashlangResult<Program> {
-    let ast = DashlangParser::parse(Rule::else_stmt, input)
-        .expect("Could not parse else statement")
-        .next()
-        .expect("Could not get else statement");
-    let ast_body = ast.into_inner().next().expect("Could not get ast body");
-    let (body_start, _) = get_pair_location(&ast_body);
-    parse_body(ast_body.as_str(), body_start + base_location)
-}
-fn parse_else_if_stmt(input: &str, base_location: usize) -> DashlangResult<If> {
-    let ast = DashlangParser::parse(Rule::else_if_stmt, input)
-        .expect("Could not parse else if statement")
-        .next()
-        .expect("Could not get else if statement");
-    let (start, end) = get_pair_location(&ast);
-    let mut inner_ast = ast.into_inner();
-
-    let ast_cond = inner_ast
-        .next()
-        .expect("Could not get else if statement condition");
-    let (cond_start, _end) = get_pair_location(&ast_cond);
-    let cond_expr = parse_expression(ast_cond.as_str(), cond_start + base_location)?;
-
-    let ast_body = inner_ast
-        .next()
-        .expect("Could not get else if statement body");
-    let (body_start, _end) = get_pair_location(&ast_body);
-    let else_if_body = parse_body(ast_body.as_str(), body_start + base_location)?;
-
-    let else_element: Option<Vec<Stmt>> = match inner_ast.next() {
-        Some(element) => {
-            let (element_start, _) = get_pair_location(&element);
-            match element.as_rule() {
-                Rule::else_stmt => Some(parse_else_stmt(
-                    element.as_str(),
-                    element_start + base_location,
-                )?),
-                Rule::else_if_stmt => Some(vec![Stmt::If(parse_else_if_stmt(
-                    element.as_str(),
-                    element_start + base_location,
-                )?)]),
-                _ => unreachable!(),
-            }
+fn parse_else(ast: Pair<Rule>) -> DashlangResult<Program> {
+    match ast.as_rule() {
+        Rule::else_stmt => {
+            let ast_body = inner_pair(ast, "ast body")?;
+            parse_body(ast_body)
         }
-        None => None,
-    };
-    Ok(If {
-        cond: cond_expr,
-        body: else_if_body,
-        else_block: else_element,
-        location: Location::new(start + base_location, end + base_location),
-    })
+        Rule::else_if_stmt => Ok(vec![Stmt::If(parse_if_stmt(ast)?)]),
+        _ => Err(unexpected_pair(&ast)),
+    }
 }
+
 #[cfg(test)]
 mod tests {
 
-    use ast::{BinaryExpr, BinaryOperator, Boolean, Expr, Int, Literal, Return, Stmt, Symbol};
+    use ast::{
+        BinaryExpr, BinaryOperator, Boolean, Expr, Int, Literal, Location, Return, Stmt, Symbol,
+    };
 
     use super::*;
+    use crate::utils::parse_rule;
 
     #[test]
     fn test_if_with_values() {
         assert_eq!(
-            parse_if_stmt("if true {}", 0),
+            parse_if_stmt(parse_rule(Rule::if_stmt, "if true {}")),
             Ok(If {
                 cond: Expr::Literal(Literal::Bool(Boolean {
                     value: true,
@@ -125,7 +65,7 @@ mod tests {
     #[test]
     fn test_if_with_symbols() {
         assert_eq!(
-            parse_if_stmt("if count < 10 {}", 0),
+            parse_if_stmt(parse_rule(Rule::if_stmt, "if count < 10 {}")),
             Ok(If {
                 cond: Expr::BinaryExpr(Box::new(BinaryExpr {
                     left: Expr::Symbol(Symbol {
@@ -148,7 +88,10 @@ mod tests {
     #[test]
     fn test_else() {
         assert_eq!(
-            parse_if_stmt("if true {return true} else {return false}", 0),
+            parse_if_stmt(parse_rule(
+                Rule::if_stmt,
+                "if true {return true} else {return false}"
+            )),
             Ok(If {
                 cond: Expr::Literal(Literal::Bool(Boolean {
                     value: true,
@@ -175,10 +118,10 @@ mod tests {
     #[test]
     fn test_if_else() {
         assert_eq!(
-            parse_if_stmt(
-                "if true {return true} else if true {return true} else {return false}",
-                0
-            ),
+            parse_if_stmt(parse_rule(
+                Rule::if_stmt,
+                "if true {return true} else if true {return true} else {return false}"
+            )),
             Ok(If {
                 cond: Expr::Literal(Literal::Bool(Boolean {
                     value: true,
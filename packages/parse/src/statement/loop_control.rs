@@ -0,0 +1,92 @@
+use ast::{Break, Continue, Location};
+use errors::DashlangResult;
+use pest::iterators::{Pair, Pairs};
+
+use crate::{parser::Rule, utils::get_pair_location};
+
+fn parse_label(pair: Pair<Rule>) -> String {
+    pair.as_str().trim_start_matches('\'').to_owned()
+}
+
+pub fn parse_loop_label(inner_ast: &mut Pairs<Rule>) -> Option<String> {
+    match inner_ast.peek() {
+        Some(pair) if pair.as_rule() == Rule::loop_label => {
+            let loop_label = inner_ast.next()?;
+            loop_label.into_inner().next().map(parse_label)
+        }
+        _ => None,
+    }
+}
+
+/// The location ends at the keyword or the label, since `label?` would take in the whitespace and
+/// comments after a bare `break` or `continue`
+fn parse_jump(ast: Pair<Rule>) -> (Location, Option<String>) {
+    let start = get_pair_location(&ast).start;
+    let mut end = start;
+    let mut label = None;
+    for pair in ast.into_inner() {
+        end = pair.as_span().end();
+        if pair.as_rule() == Rule::label {
+            label = Some(parse_label(pair));
+        }
+    }
+    (Location::new(start, end), label)
+}
+
+pub fn parse_break_stmt(ast: Pair<Rule>) -> DashlangResult<Break> {
+    let (location, label) = parse_jump(ast);
+    Ok(Break { location, label })
+}
+
+pub fn parse_continue_stmt(ast: Pair<Rule>) -> DashlangResult<Continue> {
+    let (location, label) = parse_jump(ast);
+    Ok(Continue { location, label })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_rule;
+
+    #[test]
+    fn test_parse_break() {
+        assert_eq!(
+            parse_break_stmt(parse_rule(Rule::break_stmt, "break")),
+            Ok(Break {
+                label: None,
+                location: (0, 5).into()
+            })
+        );
+        assert_eq!(
+            parse_break_stmt(parse_rule(Rule::break_stmt, "break // done")),
+            Ok(Break {
+                label: None,
+                location: (0, 5).into()
+            })
+        );
+        assert_eq!(
+            parse_break_stmt(parse_rule(Rule::break_stmt, "break 'outer")),
+            Ok(Break {
+                label: Some("outer".to_owned()),
+                location: (0, 12).into()
+            })
+        );
+    }
+    #[test]
+    fn test_parse_continue() {
+        assert_eq!(
+            parse_continue_stmt(parse_rule(Rule::continue_stmt, "continue")),
+            Ok(Continue {
+                label: None,
+                location: (0, 8).into()
+            })
+        );
+        assert_eq!(
+            parse_continue_stmt(parse_rule(Rule::continue_stmt, "continue /* skip */ 'rows")),
+            Ok(Continue {
+                label: Some("rows".to_owned()),
+                location: (0, 25).into()
+            })
+        );
+    }
+}
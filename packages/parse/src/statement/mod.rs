@@ -1,63 +1,62 @@
+mod for_in_stmt;
 mod for_stmt;
-mod if_stmt;
+pub mod if_stmt;
+mod loop_control;
 mod return_stmt;
+mod scope_declaration;
 mod while_stmt;
 
 use ast::Stmt;
 use errors::DashlangResult;
-use pest::Parser;
+use pest::iterators::Pair;
 
 use crate::{
     expression::parse_expression,
-    parser::{DashlangParser, Rule},
-    utils::get_pair_location,
+    parser::Rule,
+    utils::{inner_pair, unexpected_pair},
 };
 
 use return_stmt::parse_return_stmt;
 
-use self::{for_stmt::parse_for_stmt, if_stmt::parse_if_stmt, while_stmt::parse_while_stmt};
+use self::{
+    for_in_stmt::parse_for_in_stmt,
+    for_stmt::parse_for_stmt,
+    if_stmt::parse_if_stmt,
+    loop_control::{parse_break_stmt, parse_continue_stmt},
+    scope_declaration::{parse_global_stmt, parse_nonlocal_stmt},
+    while_stmt::parse_while_stmt,
+};
 
-pub fn parse_statement(input: &str, base_location: usize) -> DashlangResult<Stmt> {
-    let ast = DashlangParser::parse(Rule::statement, input)
-        .expect("Could not parse statement")
-        .next()
-        .expect("Could not parse statement");
-    let ast_statement = ast.into_inner().next().expect("Could not get statement");
-    let (statement_start, _) = get_pair_location(&ast_statement);
+pub fn parse_statement(ast: Pair<Rule>) -> DashlangResult<Stmt> {
+    let ast_statement = inner_pair(ast, "statement")?;
     Ok(match ast_statement.as_rule() {
-        Rule::return_stmt => {
-            parse_return_stmt(ast_statement.as_str(), statement_start + base_location)?
-        }
-        Rule::if_stmt => Stmt::If(parse_if_stmt(
-            ast_statement.as_str(),
-            statement_start + base_location,
-        )?),
-        Rule::while_stmt => Stmt::While(parse_while_stmt(
-            ast_statement.as_str(),
-            statement_start + base_location,
-        )?),
-        Rule::for_stmt => Stmt::For(Box::new(parse_for_stmt(
-            ast_statement.as_str(),
-            statement_start + base_location,
-        )?)),
-        Rule::expression => Stmt::Expr(parse_expression(
-            ast_statement.as_str(),
-            statement_start + base_location,
-        )?),
-        _ => unreachable!(),
+        Rule::return_stmt => parse_return_stmt(ast_statement)?,
+        Rule::if_stmt => Stmt::If(parse_if_stmt(ast_statement)?),
+        Rule::while_stmt => Stmt::While(parse_while_stmt(ast_statement)?),
+        Rule::for_stmt => Stmt::For(Box::new(parse_for_stmt(ast_statement)?)),
+        Rule::for_in_stmt => Stmt::ForIn(Box::new(parse_for_in_stmt(ast_statement)?)),
+        Rule::break_stmt => Stmt::Break(parse_break_stmt(ast_statement)?),
+        Rule::continue_stmt => Stmt::Continue(parse_continue_stmt(ast_statement)?),
+        Rule::nonlocal_stmt => Stmt::Nonlocal(parse_nonlocal_stmt(ast_statement)?),
+        Rule::global_stmt => Stmt::Global(parse_global_stmt(ast_statement)?),
+        Rule::expression => Stmt::Expr(parse_expression(ast_statement)?),
+        _ => return Err(unexpected_pair(&ast_statement)),
     })
 }
 #[cfg(test)]
 mod tests {
     use ast::{
-        BinaryExpr, BinaryOperator, Expr, If, Int, Literal, Location, Return, Stmt, Symbol, While,
+        BinaryExpr, BinaryOperator, Expr, If, Int, Literal, Location, Return, Stmt, SubExpr,
+        Symbol, While,
     };
+    use pest::Parser;
 
     use super::*;
+    use crate::{parser::DashlangParser, utils::parse_rule};
     #[test]
     fn test_parse_ret_stmt() {
         assert_eq!(
-            parse_statement("return 5", 0),
+            parse_statement(parse_rule(Rule::statement, "return 5")),
             Ok(Stmt::Return(Return {
                 value: Expr::Literal(Literal::Int(Int {
                     value: 5,
@@ -68,9 +67,22 @@ mod tests {
         );
     }
     #[test]
+    fn test_parse_stmt_with_comments() {
+        assert_eq!(
+            parse_statement(parse_rule(Rule::statement, "return /* value */ 5 // done")),
+            Ok(Stmt::Return(Return {
+                value: Expr::Literal(Literal::Int(Int {
+                    value: 5,
+                    location: Location::new(19, 20)
+                })),
+                location: Location::new(0, 20)
+            }))
+        );
+    }
+    #[test]
     fn test_parse_if() {
         assert_eq!(
-            parse_statement("if count < 5 {}", 0),
+            parse_statement(parse_rule(Rule::statement, "if count < 5 {}")),
             Ok(Stmt::If(If {
                 cond: Expr::BinaryExpr(Box::new(BinaryExpr {
                     left: Expr::Symbol(Symbol {
@@ -93,8 +105,9 @@ mod tests {
     #[test]
     fn test_parse_while() {
         assert_eq!(
-            parse_statement("while count < 5 {}", 0),
+            parse_statement(parse_rule(Rule::statement, "while count < 5 {}")),
             Ok(Stmt::While(While {
+                label: None,
                 cond: Expr::BinaryExpr(Box::new(BinaryExpr {
                     left: Expr::Symbol(Symbol {
                         value: String::from("count"),
@@ -112,4 +125,49 @@ mod tests {
             }))
         );
     }
+    #[test]
+    fn test_bracketed_conditions() {
+        let flag = |start| {
+            Expr::SubExpr(SubExpr {
+                value: Box::new(Expr::Symbol(Symbol {
+                    value: String::from("flag"),
+                    location: Location::new(start + 1, start + 5),
+                })),
+                location: Location::new(start, start + 6),
+            })
+        };
+        assert_eq!(
+            parse_statement(parse_rule(Rule::statement, "while (flag) {}")),
+            Ok(Stmt::While(While {
+                label: None,
+                cond: flag(6),
+                body: vec![],
+                location: Location::new(0, 15),
+            }))
+        );
+        assert_eq!(
+            parse_statement(parse_rule(Rule::statement, "if (flag) {}")),
+            Ok(Stmt::If(If {
+                cond: flag(3),
+                body: vec![],
+                else_block: None,
+                location: Location::new(0, 12),
+            }))
+        );
+    }
+    #[test]
+    fn test_keywords_are_reserved() {
+        for source in [
+            "while { }",
+            "for x in { }",
+            "if = 1",
+            "step = 2",
+            "x = {match: 1}",
+        ] {
+            assert!(
+                DashlangParser::parse(Rule::file, source).is_err(),
+                "{source} should not parse"
+            );
+        }
+    }
 }
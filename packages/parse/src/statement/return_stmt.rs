@@ -1,39 +1,19 @@
-use ast::{Expr, Location, Return, Stmt};
+use ast::{Return, Stmt};
 use errors::DashlangResult;
-use pest::Parser;
+use pest::iterators::Pair;
 
 use crate::{
     expression::parse_expression,
-    literal::parse_literal,
-    parser::{DashlangParser, Rule},
-    utils::get_pair_location,
+    parser::Rule,
+    utils::{get_pair_location, inner_pair},
 };
 
-pub fn parse_return_stmt(input: &str, base_location: usize) -> DashlangResult<Stmt> {
-    let ast = DashlangParser::parse(Rule::return_stmt, input)
-        .expect("Could not parse return statement")
-        .next()
-        .expect("Could not parse return statement");
-    let (start, end) = get_pair_location(&ast);
-    let return_stmt = ast
-        .into_inner()
-        .next()
-        .expect("Could not get return statement");
-    let (ret_start, _) = get_pair_location(&return_stmt);
-    let return_value = match return_stmt.as_rule() {
-        Rule::literal => {
-            let value = return_stmt
-                .into_inner()
-                .next()
-                .expect("Could not get value");
-            Expr::Literal(parse_literal(value.as_str(), ret_start + base_location)?)
-        }
-        Rule::expression => parse_expression(return_stmt.as_str(), ret_start + base_location)?,
-        _ => unreachable!(),
-    };
+pub fn parse_return_stmt(ast: Pair<Rule>) -> DashlangResult<Stmt> {
+    let location = get_pair_location(&ast);
+    let ast_value = inner_pair(ast, "return statement value")?;
     Ok(Stmt::Return(Return {
-        value: return_value,
-        location: Location::new(start + base_location, end + base_location),
+        value: parse_expression(ast_value)?,
+        location,
     }))
 }
 
@@ -42,10 +22,11 @@ mod tests {
     use ast::{BinaryExpr, BinaryOperator, Expr, Int, Literal, Location, Return};
 
     use super::*;
+    use crate::utils::parse_rule;
     #[test]
     fn test_return_value() {
         assert_eq!(
-            parse_return_stmt("return 1", 0),
+            parse_return_stmt(parse_rule(Rule::return_stmt, "return 1")),
             Ok(Stmt::Return(Return {
                 value: Expr::Literal(Literal::Int(Int {
                     value: 1,
@@ -58,7 +39,7 @@ mod tests {
     #[test]
     fn test_return_expression() {
         assert_eq!(
-            parse_return_stmt("return 1 + 1", 0),
+            parse_return_stmt(parse_rule(Rule::return_stmt, "return 1 + 1")),
             Ok(Stmt::Return(Return {
                 value: Expr::BinaryExpr(Box::new(BinaryExpr {
                     left: Expr::Literal(Literal::Int(Int {
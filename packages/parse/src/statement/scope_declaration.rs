@@ -0,0 +1,55 @@
+use ast::{Global, Nonlocal};
+use errors::DashlangResult;
+use pest::iterators::Pair;
+
+use crate::{parser::Rule, utils::get_pair_location};
+
+fn parse_declared_symbols(ast: Pair<Rule>) -> Vec<String> {
+    ast.into_inner()
+        .filter(|pair| pair.as_rule() == Rule::symbol)
+        .map(|pair| pair.as_str().to_owned())
+        .collect()
+}
+
+pub fn parse_nonlocal_stmt(ast: Pair<Rule>) -> DashlangResult<Nonlocal> {
+    Ok(Nonlocal {
+        location: get_pair_location(&ast),
+        symbols: parse_declared_symbols(ast),
+    })
+}
+
+pub fn parse_global_stmt(ast: Pair<Rule>) -> DashlangResult<Global> {
+    Ok(Global {
+        location: get_pair_location(&ast),
+        symbols: parse_declared_symbols(ast),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ast::Location;
+
+    use super::*;
+    use crate::utils::parse_rule;
+
+    #[test]
+    fn test_parse_nonlocal() {
+        assert_eq!(
+            parse_nonlocal_stmt(parse_rule(Rule::nonlocal_stmt, "nonlocal count, total")),
+            Ok(Nonlocal {
+                symbols: vec![String::from("count"), String::from("total")],
+                location: Location::new(0, 21)
+            })
+        );
+    }
+    #[test]
+    fn test_parse_global() {
+        assert_eq!(
+            parse_global_stmt(parse_rule(Rule::global_stmt, "global config")),
+            Ok(Global {
+                symbols: vec![String::from("config")],
+                location: Location::new(0, 13)
+            })
+        );
+    }
+}
@@ -1,48 +1,43 @@
-use ast::{Location, While};
+use ast::While;
 use errors::DashlangResult;
-use pest::Parser;
+use pest::iterators::Pair;
 
 use crate::{
     body::parse_body,
-    expression::parse_expression,
-    parser::{DashlangParser, Rule},
-    utils::get_pair_location,
+    expression::parse_condition,
+    parser::Rule,
+    utils::{get_pair_location, next_pair},
 };
 
-pub fn parse_while_stmt(input: &str, base_location: usize) -> DashlangResult<While> {
-    let ast = DashlangParser::parse(Rule::while_stmt, input)
-        .expect("Could not parse while loop")
-        .next()
-        .unwrap();
-    let (start, end) = get_pair_location(&ast);
+use super::loop_control::parse_loop_label;
+
+pub fn parse_while_stmt(ast: Pair<Rule>) -> DashlangResult<While> {
+    let location = get_pair_location(&ast);
     let mut inner_ast = ast.into_inner();
-    let ast_cond = inner_ast
-        .next()
-        .expect("Could not get while statement condition");
-    let (cond_start, _) = get_pair_location(&ast_cond);
-    let parsed_cond = parse_expression(ast_cond.as_str(), cond_start + base_location)?;
-    let ast_body = inner_ast
-        .next()
-        .expect("Could not get while statement body");
-    let (body_start, _) = get_pair_location(&ast_body);
-    let parsed_body = parse_body(ast_body.as_str(), body_start + base_location)?;
+    let label = parse_loop_label(&mut inner_ast);
+    let ast_cond = next_pair(&mut inner_ast, location, "while statement condition")?;
+    let ast_body = next_pair(&mut inner_ast, location, "while statement body")?;
     Ok(While {
-        cond: parsed_cond,
-        body: parsed_body,
-        location: Location::new(start + base_location, end + base_location),
+        label,
+        cond: parse_condition(ast_cond)?,
+        body: parse_body(ast_body)?,
+        location,
     })
 }
+
 #[cfg(test)]
 mod tests {
-    use ast::{BinaryExpr, BinaryOperator, Boolean, Expr, Int, Literal, Symbol};
+    use ast::{BinaryExpr, BinaryOperator, Boolean, Expr, Int, Literal, Location, Symbol};
 
     use super::*;
+    use crate::utils::parse_rule;
 
     #[test]
     fn test_while_with_values() {
         assert_eq!(
-            parse_while_stmt("while true {}", 0),
+            parse_while_stmt(parse_rule(Rule::while_stmt, "while true {}")),
             Ok(While {
+                label: None,
                 cond: Expr::Literal(Literal::Bool(Boolean {
                     value: true,
                     location: Location::new(6, 10)
@@ -53,10 +48,26 @@ mod tests {
         );
     }
     #[test]
+    fn test_labeled_while() {
+        assert_eq!(
+            parse_while_stmt(parse_rule(Rule::while_stmt, "'outer: while true {}")),
+            Ok(While {
+                label: Some("outer".to_owned()),
+                cond: Expr::Literal(Literal::Bool(Boolean {
+                    value: true,
+                    location: Location::new(14, 18)
+                })),
+                body: vec![],
+                location: Location::new(0, 21),
+            })
+        );
+    }
+    #[test]
     fn test_parse_while() {
         assert_eq!(
-            parse_while_stmt("while count < 10 {}", 0),
+            parse_while_stmt(parse_rule(Rule::while_stmt, "while count < 10 {}")),
             Ok(While {
+                label: None,
                 cond: Expr::BinaryExpr(Box::new(BinaryExpr {
                     left: Expr::Symbol(Symbol {
                         value: String::from("count"),
@@ -0,0 +1,197 @@
+use ast::Location;
+use errors::{DashlangError, ErrorKind};
+use pest::error::{Error, ErrorVariant, InputLocation};
+
+use crate::parser::Rule;
+
+/// Keeps grammar rule names out of error messages
+fn describe_rule(rule: Rule) -> String {
+    match rule {
+        Rule::EOI => "end of file",
+        Rule::expression
+        | Rule::binary_expression
+        | Rule::range_expression
+        | Rule::range_step
+        | Rule::match_expression
+        | Rule::if_expression
+        | Rule::block_expression
+        | Rule::postfix_expression
+        | Rule::unary_expression
+        | Rule::unary_operator
+        | Rule::unary_not
+        | Rule::unary_bitwise_not
+        | Rule::unary_neg
+        | Rule::sub_expression
+        | Rule::call_arg
+        | Rule::literal
+        | Rule::interpolated_string
+        | Rule::string
+        | Rule::int
+        | Rule::float
+        | Rule::boolean
+        | Rule::null
+        | Rule::atom
+        | Rule::vector
+        | Rule::tuple
+        | Rule::map
+        | Rule::closure => "an expression",
+        Rule::symbol | Rule::assignment_target => "a name",
+        Rule::add
+        | Rule::sub
+        | Rule::mul
+        | Rule::div
+        | Rule::int_div
+        | Rule::modulo
+        | Rule::pow
+        | Rule::ge
+        | Rule::gt
+        | Rule::le
+        | Rule::lt
+        | Rule::eq
+        | Rule::ne
+        | Rule::or
+        | Rule::and
+        | Rule::bitwise_and
+        | Rule::bitwise_or
+        | Rule::bitwise_xor
+        | Rule::bitwise_left_shift
+        | Rule::bitwise_right_shift => "an operator",
+        Rule::range_exclusive => "`..`",
+        Rule::range_inclusive => "`..=`",
+        Rule::step_keyword => "`step`",
+        Rule::in_keyword => "`in`",
+        Rule::index_access => "`[`",
+        Rule::member_access => "`.`",
+        Rule::call_args => "`(`",
+        Rule::call_expression => "a call",
+        Rule::piping_operation => "`|>`",
+        Rule::program
+        | Rule::statement
+        | Rule::return_stmt
+        | Rule::if_stmt
+        | Rule::while_stmt
+        | Rule::for_stmt
+        | Rule::for_in_stmt
+        | Rule::break_stmt
+        | Rule::continue_stmt
+        | Rule::nonlocal_stmt
+        | Rule::global_stmt
+        | Rule::assignment_expression
+        | Rule::compound_assignment_expr
+        | Rule::destructuring_assignment
+        | Rule::dash_expr => "a statement",
+        Rule::body => "`{`",
+        Rule::else_stmt | Rule::else_if_stmt => "`else`",
+        Rule::closure_params => "a parameter name",
+        Rule::map_attribute | Rule::map_pattern_field => "a map field",
+        Rule::pattern
+        | Rule::wildcard_pattern
+        | Rule::literal_pattern
+        | Rule::tuple_pattern
+        | Rule::vector_pattern
+        | Rule::map_pattern
+        | Rule::binding_pattern
+        | Rule::destructuring_pattern => "a pattern",
+        Rule::rest_pattern => "`...`",
+        Rule::match_arm => "a match arm",
+        Rule::match_guard => "`if` or `->`",
+        Rule::match_keyword => "`match`",
+        Rule::label | Rule::loop_label => "a loop label",
+        Rule::interpolation => "`${`",
+        Rule::tuple_index => "a tuple index",
+        Rule::atom_value => "an atom name",
+        Rule::text | Rule::multiline_text | Rule::raw_text => "the end of the string",
+        other => return format!("{other:?}").replace('_', " "),
+    }
+    .to_owned()
+}
+
+fn is_statement_boundary(rule: Rule) -> bool {
+    matches!(
+        rule,
+        Rule::EOI | Rule::file | Rule::program | Rule::statement
+    )
+}
+
+fn is_pattern(rule: Rule) -> bool {
+    matches!(
+        rule,
+        Rule::pattern
+            | Rule::binding_pattern
+            | Rule::destructuring_pattern
+            | Rule::wildcard_pattern
+            | Rule::literal_pattern
+            | Rule::tuple_pattern
+            | Rule::vector_pattern
+            | Rule::map_pattern
+            | Rule::rest_pattern
+            | Rule::map_pattern_field
+    )
+}
+
+/// Patterns are only mentioned when an expression can't go there, since that's what was most likely
+/// being written
+fn join_descriptions(rules: &[Rule]) -> String {
+    let expects_expression = rules
+        .iter()
+        .any(|rule| !is_pattern(*rule) && describe_rule(*rule) == "an expression");
+    let mut descriptions: Vec<String> = vec![];
+    let rules = rules
+        .iter()
+        .copied()
+        .filter(|rule| !(expects_expression && is_pattern(*rule)));
+    for description in rules.map(describe_rule) {
+        if !descriptions.contains(&description) {
+            descriptions.push(description);
+        }
+    }
+    match descriptions.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+    }
+}
+
+fn describe_found(input: &str, pos: usize) -> String {
+    let rest = input.get(pos..).unwrap_or_default();
+    let word: String = rest
+        .chars()
+        .take_while(|ch| ch.is_alphanumeric() || *ch == '_')
+        .collect();
+    if !word.is_empty() {
+        return format!("`{word}`");
+    }
+    match rest.chars().next() {
+        None => "end of file".to_owned(),
+        Some('\n') => "end of line".to_owned(),
+        Some(ch) => format!("`{ch}`"),
+    }
+}
+
+pub fn from_pest_error(err: Error<Rule>, input: &str) -> DashlangError {
+    let pos = match err.location {
+        InputLocation::Pos(pos) => pos,
+        InputLocation::Span((start, _)) => start,
+    };
+    let end = input
+        .get(pos..)
+        .and_then(|rest| rest.chars().next())
+        .map_or(pos, |ch| pos + ch.len_utf8());
+    let found = describe_found(input, pos);
+    let message = match err.variant {
+        ErrorVariant::ParsingError {
+            positives,
+            negatives,
+        } => match (positives.is_empty(), negatives.is_empty()) {
+            // Anything could follow at a statement boundary, so listing every option doesn't help
+            (false, _) if positives.iter().any(|rule| is_statement_boundary(*rule)) => {
+                format!("Unexpected {found}")
+            }
+            (false, _) => format!("Expected {}, found {found}", join_descriptions(&positives)),
+            (true, false) => format!("Unexpected {}", join_descriptions(&negatives)),
+            (true, true) => format!("Unexpected {found}"),
+        },
+        ErrorVariant::CustomError { message } => message,
+    };
+    DashlangError::new(&message, ErrorKind::Syntax).location(Location::new(pos, end))
+}
@@ -1,8 +1,107 @@
-use pest::iterators::Pair;
+use std::cell::Cell;
 
-use crate::parser::Rule;
+use ast::Location;
+use errors::{DashlangError, DashlangResult, ErrorKind};
+use pest::iterators::{Pair, Pairs};
 
-pub fn get_pair_location(pair: &Pair<Rule>) -> (usize, usize) {
+use crate::{parser::Rule, recovery::comment_len};
+
+/// Operations an expression can be nested in, counting each operator of a chain since the AST is
+/// left leaning. Building, validating and dropping the AST all recurse once per level
+const MAX_EXPRESSION_DEPTH: usize = 128;
+
+thread_local! {
+    static EXPRESSION_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Levels of the expression being built, given back when dropped
+pub struct DepthGuard(usize);
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        EXPRESSION_DEPTH.with(|depth| depth.set(depth.get() - self.0));
+    }
+}
+
+pub fn enter_expression(levels: usize, location: Location) -> DashlangResult<DepthGuard> {
+    EXPRESSION_DEPTH.with(|depth| {
+        let nested = depth.get() + levels;
+        if nested > MAX_EXPRESSION_DEPTH {
+            return Err(DashlangError::new(
+                &format!(
+                    "Expression too complex: it can't nest more than {MAX_EXPRESSION_DEPTH} operations"
+                ),
+                ErrorKind::Syntax,
+            )
+            .location(location));
+        }
+        depth.set(nested);
+        Ok(DepthGuard(levels))
+    })
+}
+
+pub fn get_pair_location(pair: &Pair<Rule>) -> Location {
+    let span = pair.as_span();
+    Location::new(span.start(), token_end(pair))
+}
+
+/// Where the last token of `pair` ends. Whitespace and comments skipped before an optional or
+/// repeated part that didn't match are still in the span, like the comment after `x = 5 // five`
+fn token_end(pair: &Pair<Rule>) -> usize {
     let span = pair.as_span();
-    (span.start(), span.end())
+    let Some(last) = pair.clone().into_inner().last() else {
+        return span.end();
+    };
+    // What's left after the last inner pair are plain tokens like closing brackets and trivia
+    let source = pair.get_input();
+    let mut end = token_end(&last);
+    let mut pos = end;
+    while pos < span.end() {
+        let rest = &source[pos..span.end()];
+        if let Some(len) = comment_len(rest) {
+            pos += len;
+            continue;
+        }
+        let ch = rest.chars().next().unwrap_or_default();
+        pos += ch.len_utf8();
+        if !ch.is_whitespace() {
+            end = pos;
+        }
+    }
+    end
+}
+
+/// Reports a malformed tree as an error instead of panicking
+pub fn next_pair<'a>(
+    pairs: &mut Pairs<'a, Rule>,
+    location: Location,
+    description: &str,
+) -> DashlangResult<Pair<'a, Rule>> {
+    pairs.next().ok_or_else(|| {
+        DashlangError::new(&format!("Could not get {description}"), ErrorKind::Unknown)
+            .location(location)
+    })
+}
+
+pub fn inner_pair<'a>(pair: Pair<'a, Rule>, description: &str) -> DashlangResult<Pair<'a, Rule>> {
+    let location = get_pair_location(&pair);
+    next_pair(&mut pair.into_inner(), location, description)
+}
+
+pub fn unexpected_pair(pair: &Pair<Rule>) -> DashlangError {
+    DashlangError::new(
+        &format!("Unexpected {:?}", pair.as_rule()),
+        ErrorKind::Unknown,
+    )
+    .location(get_pair_location(pair))
+}
+
+#[cfg(test)]
+pub fn parse_rule(rule: Rule, input: &str) -> Pair<'_, Rule> {
+    use crate::parser::DashlangParser;
+    use pest::Parser;
+
+    DashlangParser::parse(rule, input)
+        .expect("Could not parse test input")
+        .next()
+        .expect("Could not parse test input")
 }
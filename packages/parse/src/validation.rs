@@ -0,0 +1,262 @@
+use ast::{Expr, Literal, Location, Program, Stmt};
+use errors::{DashlangError, DashlangResult, ErrorKind};
+
+#[derive(Debug, PartialEq)]
+enum Enclosing {
+    Loop(Option<String>),
+    /// Jumping out of it would leave the expression around it without a value
+    ValueBlock,
+}
+
+/// Closure bodies start over, since they can't jump out of the caller's loops. Reports the first
+/// error of each top level statement
+pub fn validate_loop_control(program: &Program) -> Vec<DashlangError> {
+    program
+        .iter()
+        .filter_map(|stmt| validate_stmt(stmt, &mut vec![]).err())
+        .collect()
+}
+
+fn validate_program(program: &Program, enclosing: &mut Vec<Enclosing>) -> DashlangResult<()> {
+    for stmt in program {
+        validate_stmt(stmt, enclosing)?;
+    }
+    Ok(())
+}
+
+fn validate_value_block(program: &Program, enclosing: &mut Vec<Enclosing>) -> DashlangResult<()> {
+    enclosing.push(Enclosing::ValueBlock);
+    let result = validate_program(program, enclosing);
+    enclosing.pop();
+    result
+}
+
+fn validate_loop_body(
+    label: &Option<String>,
+    body: &Program,
+    enclosing: &mut Vec<Enclosing>,
+) -> DashlangResult<()> {
+    enclosing.push(Enclosing::Loop(label.clone()));
+    let result = validate_program(body, enclosing);
+    enclosing.pop();
+    result
+}
+
+fn jump_out_of_value_block(keyword: &str, location: Location) -> DashlangError {
+    DashlangError::new(
+        &format!("'{keyword}' can't jump out of a block used as a value"),
+        ErrorKind::Syntax,
+    )
+    .location(location)
+}
+
+fn validate_jump(
+    keyword: &str,
+    label: &Option<String>,
+    location: Location,
+    enclosing: &[Enclosing],
+) -> DashlangResult<()> {
+    let target = enclosing.iter().rposition(|item| match item {
+        Enclosing::Loop(loop_label) => label.is_none() || loop_label == label,
+        Enclosing::ValueBlock => false,
+    });
+    let Some(target) = target else {
+        let inside_loop = enclosing
+            .iter()
+            .any(|item| matches!(item, Enclosing::Loop(_)));
+        return Err(match label {
+            Some(label) if inside_loop => DashlangError::new(
+                &format!("Could not find a loop labeled '{label}"),
+                ErrorKind::Syntax,
+            ),
+            _ => DashlangError::new(
+                &format!("'{keyword}' used outside of a loop"),
+                ErrorKind::Syntax,
+            ),
+        }
+        .location(location));
+    };
+    if enclosing[target..].contains(&Enclosing::ValueBlock) {
+        return Err(jump_out_of_value_block(keyword, location));
+    }
+    Ok(())
+}
+
+fn validate_stmt(stmt: &Stmt, enclosing: &mut Vec<Enclosing>) -> DashlangResult<()> {
+    match stmt {
+        Stmt::Return(ret) => {
+            if enclosing.contains(&Enclosing::ValueBlock) {
+                return Err(jump_out_of_value_block("return", ret.location));
+            }
+            validate_expr(&ret.value, enclosing)
+        }
+        Stmt::If(if_stmt) => {
+            validate_expr(&if_stmt.cond, enclosing)?;
+            validate_program(&if_stmt.body, enclosing)?;
+            if let Some(else_block) = &if_stmt.else_block {
+                validate_program(else_block, enclosing)?;
+            }
+            Ok(())
+        }
+        Stmt::While(while_stmt) => {
+            validate_expr(&while_stmt.cond, enclosing)?;
+            validate_loop_body(&while_stmt.label, &while_stmt.body, enclosing)
+        }
+        Stmt::For(for_stmt) => {
+            validate_stmt(&for_stmt.init, enclosing)?;
+            validate_expr(&for_stmt.cond, enclosing)?;
+            validate_stmt(&for_stmt.iteration, enclosing)?;
+            validate_loop_body(&for_stmt.label, &for_stmt.body, enclosing)
+        }
+        Stmt::ForIn(for_in) => {
+            validate_expr(&for_in.iterable, enclosing)?;
+            validate_loop_body(&for_in.label, &for_in.body, enclosing)
+        }
+        Stmt::Break(break_stmt) => {
+            validate_jump("break", &break_stmt.label, break_stmt.location, enclosing)
+        }
+        Stmt::Continue(continue_stmt) => validate_jump(
+            "continue",
+            &continue_stmt.label,
+            continue_stmt.location,
+            enclosing,
+        ),
+        Stmt::Nonlocal(_) | Stmt::Global(_) => Ok(()),
+        Stmt::Expr(expr) => validate_expr(expr, enclosing),
+    }
+}
+
+fn validate_exprs<'a>(
+    exprs: impl IntoIterator<Item = &'a Expr>,
+    enclosing: &mut Vec<Enclosing>,
+) -> DashlangResult<()> {
+    for expr in exprs {
+        validate_expr(expr, enclosing)?;
+    }
+    Ok(())
+}
+
+fn validate_expr(expr: &Expr, enclosing: &mut Vec<Enclosing>) -> DashlangResult<()> {
+    match expr {
+        Expr::BinaryExpr(bin) => validate_exprs([&bin.left, &bin.right], enclosing),
+        Expr::UnaryExpr(unary) => validate_expr(&unary.operand, enclosing),
+        Expr::Assignment(assign) => {
+            validate_exprs(&assign.path, enclosing)?;
+            validate_expr(&assign.value, enclosing)
+        }
+        Expr::Call(call) => {
+            validate_expr(&call.callee, enclosing)?;
+            validate_exprs(&call.args, enclosing)
+        }
+        Expr::Symbol(_) => Ok(()),
+        Expr::Literal(literal) => match literal {
+            Literal::Closure(closure) => validate_program(&closure.body, &mut vec![]),
+            Literal::Vector(vector) => validate_exprs(&vector.value, enclosing),
+            Literal::Tuple(tuple) => validate_exprs(&tuple.value, enclosing),
+            Literal::Map(map) => validate_exprs(map.value.values(), enclosing),
+            _ => Ok(()),
+        },
+        Expr::SubExpr(sub) => validate_expr(&sub.value, enclosing),
+        Expr::DestructuringAsignment(dest) => validate_expr(&dest.value, enclosing),
+        Expr::StringInterpolation(interpolation) => validate_exprs(&interpolation.parts, enclosing),
+        Expr::Access(access) => validate_exprs([&access.target, &access.key], enclosing),
+        Expr::Range(range) => {
+            validate_exprs([&range.start, &range.end], enclosing)?;
+            validate_exprs(&range.step, enclosing)
+        }
+        Expr::Match(match_expr) => {
+            validate_expr(&match_expr.value, enclosing)?;
+            for arm in &match_expr.arms {
+                validate_exprs(&arm.guard, enclosing)?;
+                validate_expr(&arm.value, enclosing)?;
+            }
+            Ok(())
+        }
+        Expr::If(if_expr) => {
+            validate_expr(&if_expr.cond, enclosing)?;
+            validate_value_block(&if_expr.body, enclosing)?;
+            if let Some(else_block) = &if_expr.else_block {
+                validate_value_block(else_block, enclosing)?;
+            }
+            Ok(())
+        }
+        Expr::Block(block) => validate_value_block(&block.body, enclosing),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parser::Rule, program::parse_program, utils::parse_rule};
+
+    fn validate(input: &str) -> DashlangResult<()> {
+        let program = parse_program(parse_rule(Rule::program, input))?;
+        match validate_loop_control(&program).into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    #[test]
+    fn test_jumps_inside_loops() {
+        assert_eq!(validate("while true { if x { break } continue }"), Ok(()));
+        assert_eq!(
+            validate("'outer: for i = 0; i < 3; i += 1 { while true { continue 'outer } }"),
+            Ok(())
+        );
+        assert_eq!(
+            validate("'rows: for row in rows { for cell in row { break 'rows } }"),
+            Ok(())
+        );
+    }
+    #[test]
+    fn test_jumps_outside_loops() {
+        assert_eq!(
+            validate("if true { break }"),
+            Err(
+                DashlangError::new("'break' used outside of a loop", ErrorKind::Syntax)
+                    .location((10, 15).into())
+            )
+        );
+        assert_eq!(
+            validate("while true { f = () { continue } }"),
+            Err(
+                DashlangError::new("'continue' used outside of a loop", ErrorKind::Syntax)
+                    .location((22, 30).into())
+            )
+        );
+    }
+    #[test]
+    fn test_unknown_label() {
+        assert_eq!(
+            validate("'outer: while true { break 'inner }"),
+            Err(
+                DashlangError::new("Could not find a loop labeled 'inner", ErrorKind::Syntax)
+                    .location((21, 33).into())
+            )
+        );
+    }
+    #[test]
+    fn test_jumps_out_of_value_blocks() {
+        assert_eq!(
+            validate("while true { a = if x { 1 } else { break } }"),
+            Err(DashlangError::new(
+                "'break' can't jump out of a block used as a value",
+                ErrorKind::Syntax
+            )
+            .location((35, 40).into()))
+        );
+        assert_eq!(
+            validate("f = () { a = { return 1 } }"),
+            Err(DashlangError::new(
+                "'return' can't jump out of a block used as a value",
+                ErrorKind::Syntax
+            )
+            .location((15, 23).into()))
+        );
+        assert_eq!(
+            validate("a = { while true { break } f = () { return 1 } }"),
+            Ok(())
+        );
+    }
+}
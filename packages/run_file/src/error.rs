@@ -30,6 +30,12 @@ impl Diagnostic for RunfileError {
                 )
             }
             ErrorKind::WrongArgs => Some(Box::new("Try fixing the number of arguments passed to this call".to_owned())),
+            ErrorKind::OutOfBounds => Some(Box::new("Check the length of the value before accessing it".to_owned())),
+            ErrorKind::MissingKey => Some(Box::new("Check if the key exists in the map, or use map_get to get null instead".to_owned())),
+            ErrorKind::Syntax => None,
+            ErrorKind::Arithmetic => Some(Box::new("Check the values in this operation. Integers must fit in 64 bits, unless big integers are enabled with --big-ints".to_owned())),
+            ErrorKind::NoMatch => Some(Box::new("Check the shape of the value. A match can end with a `_` arm to handle anything else".to_owned())),
+            ErrorKind::PatternMismatch => Some(Box::new("Check the shape of the value. Use a match when it can have different shapes".to_owned())),
             ErrorKind::Unknown => None
 
         }
@@ -50,6 +56,12 @@ impl Diagnostic for RunfileError {
                         ErrorKind::NonCallable => "Non-callable value here",
                         ErrorKind::InvalidOperation => "Invalid operation here",
                         ErrorKind::WrongArgs => "Wrong args passed here",
+                        ErrorKind::OutOfBounds => "Index out of bounds here",
+                        ErrorKind::MissingKey => "Missing key here",
+                        ErrorKind::Syntax => "Syntax error here",
+                        ErrorKind::Arithmetic => "Arithmetic error here",
+                        ErrorKind::NoMatch => "No arm matches this value",
+                        ErrorKind::PatternMismatch => "Pattern mismatch here",
                     },
                 )]
                 .into_iter(),
@@ -58,4 +70,28 @@ impl Diagnostic for RunfileError {
     }
 }
 
+#[derive(Error, Debug)]
+pub struct RunfileErrors {
+    pub src: NamedSource<String>,
+    pub errors: Vec<RunfileError>,
+}
+
+impl Display for RunfileErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Found {} errors", self.errors.len())
+    }
+}
+
+impl Diagnostic for RunfileErrors {
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.src)
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        Some(Box::new(
+            self.errors.iter().map(|err| err as &dyn Diagnostic),
+        ))
+    }
+}
+
 pub type RunfileResult = Result<()>;
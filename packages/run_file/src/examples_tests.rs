@@ -8,7 +8,7 @@ use eval::{
 
 use crate::{error::RunfileResult, run_file};
 
-fn get_examples_folder_path() -> PathBuf {
+pub fn get_examples_folder_path() -> PathBuf {
     let current_dir = env::current_dir().unwrap();
     let examples_folder_path = current_dir
         .parent()
@@ -28,6 +28,10 @@ fn run_example(program_name: &str) -> RunfileResult {
     let mut ctx = Context::new(scope);
     ctx.use_plugin(Stdlib::new());
     ctx.use_plugin(Stdio::new());
+    // These examples need more than 64 bits, like they would be run with `--big-ints`
+    if program_name.starts_with("big_") {
+        ctx.use_big_ints();
+    }
     run_file(
         get_example_program_path(program_name).to_str().unwrap(),
         &mut ctx,
@@ -0,0 +1,200 @@
+use std::fs::{read_dir, read_to_string};
+
+use eval::{
+    ctx::Context,
+    scope::HashScope,
+    stdlib::{stdio::Stdio, Stdlib},
+};
+use parse::parse;
+use proptest::{collection::vec, prelude::*, sample::Index};
+
+use crate::examples_tests::get_examples_folder_path;
+
+const TOKENS: &[&str] = &[
+    "(", ")", "{", "}", "[", "]", ",", ".", ":", ";", "=", "+=", "|>", "|>=", "!", "~", "-", "0",
+    "1.5", "\"", "\\", "${", "r#\"", "\"\"\"", "//", "/*", "*/", "'", "'outer:", "return", "if",
+    "else", "while", "for", "break", "continue", "nonlocal", "global", "null", "\n", "é",
+];
+
+const OPERATORS: &[&str] = &[
+    "+", "-", "*", "/", "~/", "%", "**", "<", "<=", ">", ">=", "==", "!=", "&&", "||", "&", "|",
+    "^", "<<", ">>",
+];
+
+const EXTENSIONS: &[&str] = &[
+    "nth", "len", "push", "map_get", "map_set", "print", "println",
+];
+
+fn example_sources() -> Vec<String> {
+    let mut sources: Vec<String> = read_dir(get_examples_folder_path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_file())
+        .map(|path| read_to_string(path).unwrap())
+        .collect();
+    sources.sort();
+    sources
+}
+
+#[derive(Debug, Clone)]
+enum Mutation {
+    Delete(Index, usize),
+    Insert(Index, &'static str),
+    Duplicate(Index, usize),
+    Truncate(Index),
+}
+
+fn mutation() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        (any::<Index>(), 1..8usize).prop_map(|(at, len)| Mutation::Delete(at, len)),
+        (any::<Index>(), prop::sample::select(TOKENS))
+            .prop_map(|(at, token)| Mutation::Insert(at, token)),
+        (any::<Index>(), 1..20usize).prop_map(|(at, len)| Mutation::Duplicate(at, len)),
+        any::<Index>().prop_map(Mutation::Truncate),
+    ]
+}
+
+fn apply_mutation(source: &str, mutation: &Mutation) -> String {
+    let mut chars: Vec<char> = source.chars().collect();
+    let position = |at: &Index| at.index(chars.len() + 1);
+    match mutation {
+        Mutation::Delete(at, len) => {
+            let start = position(at);
+            let end = (start + len).min(chars.len());
+            chars.drain(start..end);
+        }
+        Mutation::Insert(at, token) => {
+            let start = position(at);
+            chars.splice(start..start, token.chars());
+        }
+        Mutation::Duplicate(at, len) => {
+            let start = position(at);
+            let end = (start + len).min(chars.len());
+            let copied: Vec<char> = chars[start..end].to_vec();
+            chars.splice(start..start, copied);
+        }
+        Mutation::Truncate(at) => {
+            let end = position(at);
+            chars.truncate(end);
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Expressions over `symbols`. Calls to closures are left to [statement], so closure bodies built
+/// from these expressions can never recurse
+fn expression(symbols: &'static [&'static str]) -> impl Strategy<Value = String> {
+    let leaf = prop_oneof![
+        any::<i64>().prop_map(|value| value.to_string()),
+        (-1000.0..1000.0f64).prop_map(|value| format!("{value:.3}")),
+        "[a-z ]{0,6}".prop_map(|value| format!("\"{value}\"")),
+        prop::sample::select(&["true", "false", "null", ":ok", ":error"][..])
+            .prop_map(String::from),
+        prop::sample::select(symbols).prop_map(String::from),
+    ];
+    leaf.prop_recursive(4, 48, 4, |inner| {
+        prop_oneof![
+            (
+                inner.clone(),
+                prop::sample::select(OPERATORS),
+                inner.clone()
+            )
+                .prop_map(|(lhs, op, rhs)| format!("{lhs} {op} {rhs}")),
+            (prop::sample::select(&["!", "~", "-"][..]), inner.clone())
+                .prop_map(|(op, operand)| format!("{op}({operand})")),
+            inner.clone().prop_map(|value| format!("({value})")),
+            vec(inner.clone(), 0..4).prop_map(|items| format!("[{}]", items.join(", "))),
+            vec(inner.clone(), 2..4).prop_map(|items| format!("({})", items.join(", "))),
+            vec(("[a-c]", inner.clone()), 0..3).prop_map(|attributes| {
+                let attributes: Vec<String> = attributes
+                    .into_iter()
+                    .map(|(key, value)| format!("{key}: {value}"))
+                    .collect();
+                format!("{{{}}}", attributes.join(", "))
+            }),
+            (inner.clone(), inner.clone()).prop_map(|(target, key)| format!("{target}[{key}]")),
+            (inner.clone(), "[a-c]|0|1").prop_map(|(target, key)| format!("{target}.{key}")),
+            (prop::sample::select(EXTENSIONS), vec(inner.clone(), 0..4))
+                .prop_map(|(name, args)| format!("{name}({})", args.join(", "))),
+            inner.prop_map(|value| format!("\"value: ${{{value}}}\"")),
+        ]
+    })
+}
+
+fn statement() -> impl Strategy<Value = String> {
+    const VARIABLES: &[&str] = &["v0", "v1", "v2", "v3"];
+    const PARAMS: &[&str] = &["a", "b"];
+    let variable = prop::sample::select(VARIABLES);
+    prop_oneof![
+        (variable.clone(), expression(VARIABLES))
+            .prop_map(|(name, value)| format!("{name} = {value}")),
+        (variable.clone(), expression(PARAMS))
+            .prop_map(|(name, value)| format!("{name} = (a, b) {{ return {value} }}")),
+        (variable.clone(), vec(expression(VARIABLES), 0..3))
+            .prop_map(|(name, args)| format!("{name}({})", args.join(", "))),
+        (
+            variable.clone(),
+            prop::sample::select(OPERATORS),
+            expression(VARIABLES)
+        )
+            .prop_map(|(name, op, value)| format!("{name} {op}= {value}")),
+        (
+            variable.clone(),
+            expression(VARIABLES),
+            expression(VARIABLES)
+        )
+            .prop_map(|(name, key, value)| format!("{name}[{key}] = {value}")),
+        (variable.clone(), variable.clone(), expression(VARIABLES))
+            .prop_map(|(first, second, value)| format!("({first}, {second}) = {value}")),
+        (expression(VARIABLES), variable, expression(VARIABLES)).prop_map(
+            |(cond, name, value)| format!("if {cond} {{ {name} = {value} }} else {{ {name} }}")
+        ),
+    ]
+}
+
+fn run(source: &str) {
+    let Ok(program) = parse(source) else {
+        return;
+    };
+    let mut ctx = Context::new(HashScope::default());
+    ctx.use_plugin(Stdlib::new());
+    ctx.use_plugin(Stdio::new());
+    let _ = ctx.run_program(program);
+}
+
+proptest! {
+    #[test]
+    fn parse_never_panics_on_random_input(source in any::<String>()) {
+        let _ = parse(&source);
+    }
+
+    #[test]
+    fn parse_never_panics_on_random_tokens(tokens in vec(prop::sample::select(TOKENS), 0..40)) {
+        let _ = parse(&tokens.join(" "));
+    }
+
+    // Mutated examples are only parsed, since breaking a loop condition could make them run forever
+    #[test]
+    fn parse_never_panics_on_mutated_examples(
+        source in prop::sample::select(example_sources()),
+        mutations in vec(mutation(), 1..6),
+    ) {
+        let mutated = mutations
+            .iter()
+            .fold(source, |source, mutation| apply_mutation(&source, mutation));
+        let _ = parse(&mutated);
+    }
+
+    #[test]
+    fn eval_never_panics_on_generated_programs(statements in vec(statement(), 1..8)) {
+        run(&statements.join("\n"));
+    }
+}
+
+#[test]
+fn parse_rejects_expressions_nested_too_deeply() {
+    let long_chain = format!("x = 1{}", " + 1".repeat(3000));
+    assert!(parse(&long_chain).is_err());
+    let prefix_run = format!("x = {}1", "!".repeat(5000));
+    assert!(parse(&prefix_run).is_err());
+}
@@ -2,29 +2,41 @@ pub mod error;
 
 #[cfg(test)]
 mod examples_tests;
+#[cfg(test)]
+mod fuzz_tests;
 
 use parse::parse;
 use std::fs::read_to_string;
 
-use error::{RunfileError, RunfileResult};
+use error::{RunfileError, RunfileErrors, RunfileResult};
 use eval::{ctx::Context, scope::Scope};
-use miette::NamedSource;
+use miette::{IntoDiagnostic, NamedSource, WrapErr};
 
 pub fn run_file<T: Scope + Clone>(file_path: &str, ctx: &mut Context<T>) -> RunfileResult {
-    let file_content = read_to_string(file_path).unwrap();
+    let file_content = read_to_string(file_path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Could not read '{file_path}'"))?;
+    let src = NamedSource::new(file_path, file_content.clone());
     match parse(&file_content) {
-        Err(err) => Err(RunfileError {
-            src: NamedSource::new(file_path, file_content),
-            err,
+        Err(mut errors) if errors.len() == 1 => Err(RunfileError {
+            src,
+            err: errors.remove(0),
+        }
+        .into()),
+        Err(errors) => Err(RunfileErrors {
+            errors: errors
+                .into_iter()
+                .map(|err| RunfileError {
+                    src: src.clone(),
+                    err,
+                })
+                .collect(),
+            src,
         }
         .into()),
         Ok(program) => match ctx.run_program(program) {
             Ok(_) => Ok(()),
-            Err(err) => Err(RunfileError {
-                src: NamedSource::new(file_path, file_content),
-                err,
-            }
-            .into()),
+            Err(err) => Err(RunfileError { src, err }.into()),
         },
     }
 }